@@ -1,12 +1,20 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, Type};
+use syn::{parse_quote, Generics, Ident, Type};
 
-use super::HAS_DIESEL;
+use super::{
+    generics::{with_param, with_predicates},
+    HAS_DIESEL,
+};
 
-pub fn diesel_impl_not_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
-    let from_sql = from_sql_not_secret(sql_type, inner, name);
-    let to_sql = to_sql_not_secret(sql_type, inner, name);
+pub fn diesel_impl_not_secret(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let from_sql = from_sql_not_secret(sql_type, inner, name, generics);
+    let to_sql = to_sql_not_secret(sql_type, inner, name, generics);
 
     if HAS_DIESEL {
         quote! {
@@ -18,9 +26,14 @@ pub fn diesel_impl_not_secret(sql_type: &Type, inner: &Type, name: &Ident) -> To
     }
 }
 
-pub fn diesel_impl_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
-    let from_sql = from_sql_secret(sql_type, inner, name);
-    let to_sql = to_sql_secret(sql_type, inner, name);
+pub fn diesel_impl_validated(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let from_sql = from_sql_validated(sql_type, inner, name, generics);
+    let to_sql = to_sql_not_secret(sql_type, inner, name, generics);
 
     if HAS_DIESEL {
         quote! {
@@ -32,46 +45,157 @@ pub fn diesel_impl_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenS
     }
 }
 
-fn from_sql_not_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
+/// `FromSql` and `ToSql` impls that go through `String`, using `FromStr` and `Display`
+///
+/// Diesel has no way to bind a temporary string for every backend, so `ToSql` needs a backend
+/// whose `Output` implements `Write` (i.e. everything but SQLite)
+pub fn diesel_impl_via_str(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let from_sql = from_sql_via_str(sql_type, name, generics);
+    let to_sql = to_sql_via_str(sql_type, inner, name, generics);
+
+    if HAS_DIESEL {
+        quote! {
+            #from_sql
+            #to_sql
+        }
+    } else {
+        quote! {}
+    }
+}
+
+pub fn diesel_impl_secret(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let from_sql = from_sql_secret(sql_type, inner, name, generics);
+    let to_sql = to_sql_secret(sql_type, inner, name, generics);
+
+    if HAS_DIESEL {
+        quote! {
+            #from_sql
+            #to_sql
+        }
+    } else {
+        quote! {}
+    }
+}
+
+/// Generics for a `FromSql` impl, with an extra `__DB` parameter for the backend
+fn from_sql_generics(sql_type: &Type, inner: &Type, generics: &Generics) -> Generics {
+    let generics = with_param(generics, parse_quote!(__DB: ::diesel::backend::Backend));
+    with_predicates(
+        &generics,
+        [parse_quote!(#inner: ::diesel::deserialize::FromSql<#sql_type, __DB>)],
+    )
+}
+
+/// Generics for a `ToSql` impl, with an extra `__DB` parameter for the backend
+fn to_sql_generics(sql_type: &Type, inner: &Type, generics: &Generics) -> Generics {
+    let generics = with_param(generics, parse_quote!(__DB: ::diesel::backend::Backend));
+    with_predicates(
+        &generics,
+        [parse_quote!(#inner: ::diesel::serialize::ToSql<#sql_type, __DB>)],
+    )
+}
+
+fn from_sql_not_secret(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_sql_generics = from_sql_generics(sql_type, inner, generics);
+    let (impl_generics, _, where_clause) = from_sql_generics.split_for_impl();
+
     quote! {
-        impl<B: ::diesel::backend::Backend> ::diesel::deserialize::FromSql<#sql_type, B> for #name
-        where
-            #inner: ::diesel::deserialize::FromSql<#sql_type, B>,
+        impl #impl_generics ::diesel::deserialize::FromSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
         {
             fn from_sql(
-                bytes: ::diesel::backend::RawValue<'_, B>,
+                bytes: ::diesel::backend::RawValue<'_, __DB>,
             ) -> ::diesel::deserialize::Result<Self> {
-                <#inner as ::diesel::deserialize::FromSql<#sql_type, B>>::from_sql(bytes).map(#name)
+                <#inner as ::diesel::deserialize::FromSql<#sql_type, __DB>>::from_sql(bytes)
+                    .map(<Self as ::microtype::Microtype>::new)
             }
         }
     }
 }
 
-fn to_sql_not_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
+fn to_sql_not_secret(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let to_sql_generics = to_sql_generics(sql_type, inner, generics);
+    let (impl_generics, _, where_clause) = to_sql_generics.split_for_impl();
+
     quote! {
-        impl<B: ::diesel::backend::Backend> ::diesel::serialize::ToSql<#sql_type, B> for #name
-        where
-            #inner: ::diesel::serialize::ToSql<#sql_type, B>,
+        impl #impl_generics ::diesel::serialize::ToSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
         {
-            fn to_sql<'b>(
-                &'b self,
-                out: &mut diesel::serialize::Output<'b, '_, B>,
+            fn to_sql<'__b>(
+                &'__b self,
+                out: &mut diesel::serialize::Output<'__b, '_, __DB>,
             ) -> diesel::serialize::Result {
-                <#inner as ::diesel::serialize::ToSql<#sql_type, B>>::to_sql(&self.0, out)
+                <#inner as ::diesel::serialize::ToSql<#sql_type, __DB>>::to_sql(&self.0, out)
             }
         }
 
     }
 }
 
-fn from_sql_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
+/// Validation errors are converted using their `Display` impl, which the `validate` docs mention
+fn from_sql_validated(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_sql_generics = from_sql_generics(sql_type, inner, generics);
+    let (impl_generics, _, where_clause) = from_sql_generics.split_for_impl();
+
     quote! {
-        impl<B: ::diesel::backend::Backend> ::diesel::deserialize::FromSql<#sql_type, B> for #name
-        where
-            #inner: ::diesel::deserialize::FromSql<#sql_type, B>,
+        impl #impl_generics ::diesel::deserialize::FromSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
         {
-            fn from_sql(bytes: ::diesel::backend::RawValue<'_, B>) -> ::diesel::deserialize::Result<Self> {
-                <#inner as ::diesel::deserialize::FromSql<#sql_type, B>>::from_sql(bytes)
+            fn from_sql(
+                bytes: ::diesel::backend::RawValue<'_, __DB>,
+            ) -> ::diesel::deserialize::Result<Self> {
+                let inner = <#inner as ::diesel::deserialize::FromSql<#sql_type, __DB>>::from_sql(bytes)?;
+                <Self as ::microtype::ValidatedMicrotype>::try_new(inner)
+                    .map_err(|e| ::microtype::__private::alloc::string::ToString::to_string(&e).into())
+            }
+        }
+    }
+}
+
+fn from_sql_secret(
+    sql_type: &Type,
+    inner: &Type,
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_sql_generics = from_sql_generics(sql_type, inner, generics);
+    let (impl_generics, _, where_clause) = from_sql_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::diesel::deserialize::FromSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
+        {
+            fn from_sql(bytes: ::diesel::backend::RawValue<'_, __DB>) -> ::diesel::deserialize::Result<Self> {
+                <#inner as ::diesel::deserialize::FromSql<#sql_type, __DB>>::from_sql(bytes)
                     .map(<Self as ::microtype::SecretMicrotype>::new)
             }
         }
@@ -79,17 +203,20 @@ fn from_sql_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
     }
 }
 
-fn to_sql_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
+fn to_sql_secret(sql_type: &Type, inner: &Type, name: &Ident, generics: &Generics) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let to_sql_generics = to_sql_generics(sql_type, inner, generics);
+    let (impl_generics, _, where_clause) = to_sql_generics.split_for_impl();
+
     quote! {
-        impl<B: ::diesel::backend::Backend> ::diesel::serialize::ToSql<#sql_type, B> for #name
-        where
-            #inner: ::diesel::serialize::ToSql<#sql_type, B>,
+        impl #impl_generics ::diesel::serialize::ToSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
         {
-            fn to_sql<'b>(
-                &'b self,
-                out: &mut diesel::serialize::Output<'b, '_, B>,
+            fn to_sql<'__b>(
+                &'__b self,
+                out: &mut diesel::serialize::Output<'__b, '_, __DB>,
             ) -> diesel::serialize::Result {
-                <#inner as ::diesel::serialize::ToSql<#sql_type, B>>::to_sql(
+                <#inner as ::diesel::serialize::ToSql<#sql_type, __DB>>::to_sql(
                     <Self as ::microtype::secrecy::ExposeSecret<#inner>>::expose_secret(&self),
                     out,
                 )
@@ -97,3 +224,51 @@ fn to_sql_secret(sql_type: &Type, inner: &Type, name: &Ident) -> TokenStream {
         }
     }
 }
+
+fn from_sql_via_str(sql_type: &Type, name: &Ident, generics: &Generics) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let string = quote!(::microtype::__private::alloc::string::String);
+    let from_sql_generics = from_sql_generics(sql_type, &parse_quote!(#string), generics);
+    let (impl_generics, _, where_clause) = from_sql_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::diesel::deserialize::FromSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
+        {
+            fn from_sql(
+                bytes: ::diesel::backend::RawValue<'_, __DB>,
+            ) -> ::diesel::deserialize::Result<Self> {
+                let s = <#string as ::diesel::deserialize::FromSql<#sql_type, __DB>>::from_sql(bytes)?;
+                <Self as ::core::str::FromStr>::from_str(&s)
+                    .map_err(|e| ::microtype::__private::alloc::string::ToString::to_string(&e).into())
+            }
+        }
+    }
+}
+
+fn to_sql_via_str(sql_type: &Type, inner: &Type, name: &Ident, generics: &Generics) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let to_sql_generics = with_param(generics, parse_quote!(__DB: ::diesel::backend::Backend));
+    let to_sql_generics = with_predicates(
+        &to_sql_generics,
+        [
+            parse_quote!(#inner: ::core::fmt::Display),
+            parse_quote!(for<'__b, '__c> ::diesel::serialize::Output<'__b, '__c, __DB>: ::microtype::__private::diesel::Write),
+        ],
+    );
+    let (impl_generics, _, where_clause) = to_sql_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::diesel::serialize::ToSql<#sql_type, __DB> for #name #ty_generics
+        #where_clause
+        {
+            fn to_sql<'__b>(
+                &'__b self,
+                out: &mut diesel::serialize::Output<'__b, '_, __DB>,
+            ) -> diesel::serialize::Result {
+                ::microtype::__private::diesel::Write::write_fmt(out, ::core::format_args!("{}", self.0))?;
+                Ok(::diesel::serialize::IsNull::No)
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote_spanned;
+use quote::{quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Path};
+
+use super::special_attrs::IdKind;
 
 pub fn serialize_without_serde(span: Span) -> TokenStream {
     quote_spanned! {
@@ -7,8 +10,210 @@ pub fn serialize_without_serde(span: Span) -> TokenStream {
     }
 }
 
+pub fn secret_serde_without_feature(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[microtype(serde = true)]` on a secret microtype requires the `serde` feature")
+    }
+}
+
 pub fn secret_feature_missing(span: Span) -> TokenStream {
     quote_spanned! {
         span => compile_error!("`#[secret] is only supported when the `secret` feature is enabled")
     }
 }
+
+pub fn secret_hash_feature_missing(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[secret(hash)]` is only supported when the `ct_eq` and `std` features are enabled")
+    }
+}
+
+pub fn validated_secret(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[validate]` is not supported on secret microtypes")
+    }
+}
+
+pub fn tagged_without_type_param(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[tagged]` microtypes need a type parameter for the tag, e.g. `Id<T>`")
+    }
+}
+
+pub fn hand_written_secret_serialize(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[secret(serialize)]` is not supported with `#[derive(Microtype)]`, derive `Serialize` on the struct instead")
+    }
+}
+
+pub fn hand_written_secret_field(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("secret microtypes with `#[derive(Microtype)]` must have a `Secret<T>` field")
+    }
+}
+
+pub fn int_options_without_ops(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(...)]` options are not supported on secret or validated microtypes, since they don't get arithmetic impls")
+    }
+}
+
+pub fn overflow_without_primitive(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(overflow = \"...\")]` is only supported when the inner type is an integer primitive (e.g. `u64`)")
+    }
+}
+
+pub fn range_without_primitive(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(range = ...)]` is only supported when the inner type is an integer primitive (e.g. `u8`)")
+    }
+}
+
+pub fn range_with_overflow(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(range = ...)]` microtypes always use checked arithmetic, so only `overflow = \"checked\"` is allowed")
+    }
+}
+
+pub fn range_validated(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(range = ...)]` can't be combined with `#[validate(...)]`, since it has its own error type")
+    }
+}
+
+pub fn public_encoding_without_unsigned(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(public_encoding = ...)]` is only supported when the inner type is an unsigned integer primitive, other than `u128`")
+    }
+}
+
+pub fn public_encoding_with_range(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[int(public_encoding = ...)]` can't be combined with `range`")
+    }
+}
+
+pub fn float_options_without_ops(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[float(...)]` options are not supported on secret or validated microtypes")
+    }
+}
+
+pub fn float_options_without_primitive(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[float(finite)]`, `#[float(total_ord)]` and `#[float(hash)]` are only supported when the inner type is `f32` or `f64`")
+    }
+}
+
+pub fn cross_ops_without_microtype(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[ops(...)]` entries can't start with secret or validated microtypes, since they don't implement `Microtype`")
+    }
+}
+
+pub fn string_options_secret(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[string(...)]` options are not supported on secret microtypes")
+    }
+}
+
+pub fn derive_conflict(path: &Path, attr: &str) -> TokenStream {
+    let message = format!(
+        "`{}` is implemented by `{attr}`, so it can't be derived as well",
+        path.to_token_stream().to_string().replace(' ', "")
+    );
+    quote_spanned! {
+        path.span() => compile_error!(#message)
+    }
+}
+
+pub fn normalize_secret(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[normalize(...)]` is not supported on secret microtypes")
+    }
+}
+
+pub fn nfc_feature_missing(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`nfc` normalization is only supported when the `nfc` feature is enabled")
+    }
+}
+
+pub fn string_constraints_validated(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[string(...)]` constraints can't be combined with `#[validate(...)]`, since they have their own error type")
+    }
+}
+
+pub fn regex_feature_missing(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`pattern` is only supported when the `regex` feature is enabled")
+    }
+}
+
+pub fn one_of_secret(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[one_of(...)]` is not supported on secret microtypes")
+    }
+}
+
+pub fn one_of_not_string(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[one_of(...)]` is only supported on string microtypes")
+    }
+}
+
+pub fn one_of_validated(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[one_of(...)]` can't be combined with `#[validate(...)]` or `#[string(...)]` constraints, since it has its own error type")
+    }
+}
+
+pub fn prefixed_id_conflict(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[prefixed_id(...)]` generates its own `FromStr`, `Display` and validation, so it can't be combined with `#[secret]`, `#[validate(...)]`, `#[one_of(...)]` or type hints")
+    }
+}
+
+pub fn id_conflict(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[id(...)]` generates its own `FromStr`, `Display` and comparison traits, so it can't be combined with `#[secret]`, `#[validate(...)]`, `#[normalize(...)]`, `#[one_of(...)]`, `#[prefixed_id(...)]` or type hints")
+    }
+}
+
+pub fn int_id_conflict(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[id(sequence)]` and `#[id(snowflake(...))]` need `#[int]` (without options, since IDs don't get arithmetic), and can't be combined with `#[secret]`, `#[validate(...)]`, `#[normalize(...)]`, `#[one_of(...)]` or `#[prefixed_id(...)]`")
+    }
+}
+
+pub fn int_id_inner(span: Span, kind: &IdKind) -> TokenStream {
+    let message = match kind {
+        IdKind::Snowflake(_) => "`#[id(snowflake(...))]` is only supported when the inner type is `i64` or `u64`",
+        _ => "`#[id(sequence)]` is only supported when the inner type is an integer primitive, other than `i128` and `u128`",
+    };
+    quote_spanned! {
+        span => compile_error!(#message)
+    }
+}
+
+pub fn id_feature_missing(span: Span, feature: &str) -> TokenStream {
+    let message =
+        format!("this `#[id(...)]` is only supported when the `{feature}` feature is enabled");
+    quote_spanned! {
+        span => compile_error!(#message)
+    }
+}
+
+pub fn checksum_conflict(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[checksum(...)]` needs `#[string]` or `#[int]` (without options, since arithmetic would break the check digits), and can't be combined with `#[secret]`, `#[validate(...)]`, `#[one_of(...)]`, `#[prefixed_id(...)]`, `#[id(...)]` or `#[string(...)]` constraints, since it has its own error type")
+    }
+}
+
+pub fn checksum_without_unsigned(span: Span) -> TokenStream {
+    quote_spanned! {
+        span => compile_error!("`#[checksum(...)]` on `#[int]` is only supported when the inner type is an unsigned integer primitive")
+    }
+}
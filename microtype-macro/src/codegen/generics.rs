@@ -0,0 +1,107 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{GenericParam, Generics, Ident, WherePredicate};
+
+/// Copy `generics`, adding extra where-predicates (e.g. requiring the inner type to implement the
+/// trait being delegated to)
+pub fn with_predicates(
+    generics: &Generics,
+    predicates: impl IntoIterator<Item = WherePredicate>,
+) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Copy `generics`, adding a parameter that only the impl uses (e.g. the diesel backend)
+///
+/// Note that the type generics of the result shouldn't be used, since the type doesn't have this
+/// parameter
+pub fn with_param(generics: &Generics, param: GenericParam) -> Generics {
+    let mut generics = generics.clone();
+    match param {
+        // lifetimes must come before other parameters
+        GenericParam::Lifetime(_) => generics.params.insert(0, param),
+        _ => generics.params.push(param),
+    }
+    generics
+}
+
+/// Copy `generics`, removing the type parameter `ident` and any where-predicates that mention it
+pub fn without_param(generics: &Generics, ident: &Ident) -> Generics {
+    let mut generics = generics.clone();
+    generics.params = generics
+        .params
+        .into_iter()
+        .filter(|param| !matches!(param, GenericParam::Type(ty) if &ty.ident == ident))
+        .collect();
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .clone()
+            .into_iter()
+            .filter(|predicate| !mentions(predicate.to_token_stream(), ident))
+            .collect();
+    }
+
+    generics
+}
+
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(other) => &other == ident,
+        TokenTree::Group(group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{quote, ToTokens};
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn adds_lifetimes_first() {
+        let generics: Generics = parse_quote!(<T: Clone>);
+        let generics = with_param(&generics, parse_quote!('de));
+        let generics = with_param(&generics, parse_quote!(D));
+        let (impl_generics, _, _) = generics.split_for_impl();
+
+        assert_eq!(
+            impl_generics.to_token_stream().to_string(),
+            quote!(<'de, T: Clone, D>).to_string()
+        );
+    }
+
+    #[test]
+    fn adds_predicates() {
+        let mut generics: Generics = parse_quote!(<T>);
+        generics.where_clause = Some(parse_quote!(where T: Clone));
+        let generics = with_predicates(&generics, [parse_quote!(Vec<T>: Default)]);
+
+        assert_eq!(
+            generics.where_clause.to_token_stream().to_string(),
+            quote!(where T: Clone, Vec<T>: Default).to_string()
+        );
+    }
+
+    #[test]
+    fn removes_param_and_its_predicates() {
+        let mut generics: Generics = parse_quote!(<'a, T: Clone, U>);
+        generics.where_clause = Some(parse_quote!(where Vec<U>: Default, T: Copy));
+        let generics = without_param(&generics, &parse_quote!(U));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        assert_eq!(
+            impl_generics.to_token_stream().to_string(),
+            quote!(<'a, T: Clone>).to_string()
+        );
+        assert_eq!(
+            where_clause.to_token_stream().to_string(),
+            quote!(where T: Copy).to_string()
+        );
+    }
+}
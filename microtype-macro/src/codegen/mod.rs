@@ -4,13 +4,31 @@ use syn::spanned::Spanned;
 use crate::model::Microtype;
 
 use self::{
-    errors::{secret_feature_missing, serialize_without_serde},
-    special_attrs::{strip_special_attrs, SecretAttr},
+    errors::{
+        checksum_conflict, checksum_without_unsigned, cross_ops_without_microtype, derive_conflict,
+        float_options_without_ops, float_options_without_primitive, hand_written_secret_serialize,
+        id_conflict, id_feature_missing, int_id_conflict, int_id_inner, int_options_without_ops,
+        nfc_feature_missing, normalize_secret, one_of_not_string, one_of_secret, one_of_validated,
+        overflow_without_primitive, prefixed_id_conflict, public_encoding_with_range,
+        public_encoding_without_unsigned, range_validated, range_with_overflow,
+        range_without_primitive, regex_feature_missing, secret_feature_missing,
+        secret_hash_feature_missing, secret_serde_without_feature, serialize_without_serde,
+        string_constraints_validated, string_options_secret, tagged_without_type_param,
+        validated_secret,
+    },
+    special_attrs::{
+        find_derive, find_unowned_cross_op, is_float_primitive, is_int_primitive,
+        strip_special_attrs, ChecksumAttr, FloatAttr, IdKind, IntAttr, Normalization, Overflow,
+        SecretAttr, StringAttr, TypeAnnotation,
+    },
 };
 
 mod diesel;
+mod generics;
 mod normal;
 mod secret;
+mod tagged;
+mod validated;
 
 mod errors;
 mod special_attrs;
@@ -20,8 +38,18 @@ const HAS_TEST_IMPLS: bool = cfg!(feature = "test_impls");
 const HAS_DEREF_IMPLS: bool = cfg!(feature = "deref_impls");
 const HAS_SECRET: bool = cfg!(feature = "secret");
 const HAS_DIESEL: bool = cfg!(feature = "diesel");
+const HAS_NFC: bool = cfg!(feature = "nfc");
+const HAS_REGEX: bool = cfg!(feature = "regex");
+const HAS_STD: bool = cfg!(feature = "std");
+const HAS_UUID: bool = cfg!(feature = "uuid");
+const HAS_ULID: bool = cfg!(feature = "ulid");
+const HAS_CT_EQ: bool = cfg!(feature = "ct_eq");
 
 pub fn codegen(microtypes: Vec<Microtype>) -> TokenStream {
+    if let Some(tokens) = find_unowned_cross_op(&microtypes) {
+        return tokens;
+    }
+
     let mut stream = TokenStream::new();
 
     for microtype in microtypes {
@@ -36,8 +64,10 @@ fn generate_single(
     Microtype {
         inner,
         name,
+        generics,
         attrs,
         vis,
+        hand_written,
     }: Microtype,
 ) -> TokenStream {
     let (attrs, special_attrs) = match strip_special_attrs(attrs) {
@@ -45,25 +75,378 @@ fn generate_single(
         Err(tokens) => return tokens,
     };
 
-    if !HAS_SERDE {
+    if !special_attrs.overrides.serde() {
         if let Some(SecretAttr {
             serialize: Some(_),
             path,
+            ..
         }) = special_attrs.secret
         {
             return serialize_without_serde(path.span());
         }
     }
 
-    match &special_attrs.secret {
-        None => normal::generate_normal(inner, name, vis, attrs, special_attrs),
-        Some(SecretAttr { path, .. }) => {
-            if HAS_SECRET {
-                secret::generate_secret(inner, name, attrs, vis, special_attrs)
-            } else {
-                secret_feature_missing(path.span())
+    // `secrecy` only implements the serde traits when the `serde` feature is enabled
+    if let (Some(SecretAttr { path, .. }), Some(true)) =
+        (&special_attrs.secret, special_attrs.overrides.serde)
+    {
+        if !HAS_SERDE {
+            return secret_serde_without_feature(path.span());
+        }
+    }
+
+    // the serde derives are on the hand-written struct, so there's nothing to add them to
+    if let (
+        Some(SecretAttr {
+            serialize: Some(serialize),
+            ..
+        }),
+        true,
+    ) = (&special_attrs.secret, hand_written)
+    {
+        return hand_written_secret_serialize(serialize.span());
+    }
+
+    // the keyed hash needs `std`'s `RandomState`, and `Eq` uses `ConstantTimeEq`
+    if let Some(SecretAttr {
+        hash: Some(hash), ..
+    }) = &special_attrs.secret
+    {
+        if !(HAS_CT_EQ && HAS_STD) {
+            return secret_hash_feature_missing(hash.span());
+        }
+        if let Some(path) = find_derive(&attrs, &["PartialEq", "Eq", "Hash"]) {
+            return derive_conflict(&path, "#[secret(hash)]");
+        }
+    }
+
+    if let Some(path) = &special_attrs.tagged {
+        if tagged::Tag::find(&generics).is_none() {
+            return tagged_without_type_param(path.span());
+        }
+        // secret microtypes only get `Clone` and `Debug` from `#[tagged]`
+        let traits: &[&str] = match special_attrs.secret {
+            Some(_) => &["Clone", "Debug"],
+            None => &[
+                "Clone",
+                "Copy",
+                "Debug",
+                "PartialEq",
+                "Eq",
+                "PartialOrd",
+                "Ord",
+                "Hash",
+            ],
+        };
+        if let Some(path) = find_derive(&attrs, traits) {
+            return derive_conflict(&path, "#[tagged]");
+        }
+    }
+
+    // `try_new` and `new_const` would disagree about which values are valid
+    if let (
+        Some(TypeAnnotation::Int(IntAttr {
+            range: Some(_),
+            options: Some(span),
+            ..
+        })),
+        Some(_),
+    ) = (&special_attrs.type_annotation, &special_attrs.validate)
+    {
+        return range_validated(*span);
+    }
+
+    // only plain microtypes get arithmetic, so the options would be silently ignored
+    if let Some(TypeAnnotation::Int(IntAttr {
+        options: Some(span),
+        ..
+    })) = &special_attrs.type_annotation
+    {
+        if special_attrs.secret.is_some() || special_attrs.validate.is_some() {
+            return int_options_without_ops(*span);
+        }
+    }
+
+    // the overflow modes use inherent methods like `wrapping_add`, which only primitives have
+    if let Some(TypeAnnotation::Int(IntAttr {
+        overflow: Some(_),
+        options: Some(span),
+        ..
+    })) = &special_attrs.type_annotation
+    {
+        if !is_int_primitive(&inner) {
+            return overflow_without_primitive(*span);
+        }
+    }
+
+    // bounded microtypes need the primitive's `MIN`, `MAX` and `checked_*` methods
+    if let Some(TypeAnnotation::Int(IntAttr {
+        range: Some(_),
+        overflow,
+        options: Some(span),
+        ..
+    })) = &special_attrs.type_annotation
+    {
+        if !is_int_primitive(&inner) {
+            return range_without_primitive(*span);
+        }
+        if !matches!(overflow, None | Some(Overflow::Checked)) {
+            return range_with_overflow(*span);
+        }
+    }
+
+    // Sqids only encodes non-negative numbers, and bounded microtypes are validated, so would need
+    // a different `FromStr` error
+    if let Some(TypeAnnotation::Int(IntAttr {
+        public_encoding: Some(encoding),
+        range,
+        ..
+    })) = &special_attrs.type_annotation
+    {
+        if !encoding.supports_inner(&inner) {
+            return public_encoding_without_unsigned(encoding.span);
+        }
+        if range.is_some() {
+            return public_encoding_with_range(encoding.span);
+        }
+    }
+
+    if let Some(TypeAnnotation::Float(FloatAttr {
+        options: Some(span),
+        finite,
+        total_ord,
+        ..
+    })) = &special_attrs.type_annotation
+    {
+        // like `#[int(...)]`, these would be silently ignored
+        if special_attrs.secret.is_some() || special_attrs.validate.is_some() {
+            return float_options_without_ops(*span);
+        }
+        // `is_finite`, `total_cmp` and `to_bits` are inherent methods on the primitives
+        if (*finite || *total_ord) && !is_float_primitive(&inner) {
+            return float_options_without_primitive(*span);
+        }
+    }
+
+    // secrets don't implement the comparison traits, so the options would be silently ignored
+    if let (
+        Some(TypeAnnotation::String(StringAttr {
+            options: Some(span),
+            ..
+        })),
+        Some(_),
+    ) = (&special_attrs.type_annotation, &special_attrs.secret)
+    {
+        return string_options_secret(*span);
+    }
+
+    if let (Some(normalizer), Some(_)) = (&special_attrs.normalize, &special_attrs.secret) {
+        return normalize_secret(normalizer.span());
+    }
+
+    // NFC needs the `unicode-normalization` crate, which is re-exported by `microtype`
+    if let Some(TypeAnnotation::String(StringAttr {
+        normalize,
+        options: Some(span),
+        ..
+    })) = &special_attrs.type_annotation
+    {
+        if normalize.contains(&Normalization::Nfc) && !HAS_NFC {
+            return nfc_feature_missing(*span);
+        }
+    }
+
+    if let Some(TypeAnnotation::String(string)) = &special_attrs.type_annotation {
+        if let (Some(pattern), false) = (&string.pattern, HAS_REGEX) {
+            return regex_feature_missing(pattern.span());
+        }
+        // the validator's error type would conflict with `StringConstraintError`
+        if let (true, Some(validate)) = (string.has_constraints(), &special_attrs.validate) {
+            return string_constraints_validated(validate.validator.span());
+        }
+    }
+
+    // otherwise, the user would get a less helpful "conflicting implementations" error
+    let comparison_impls = match &special_attrs.type_annotation {
+        Some(TypeAnnotation::String(StringAttr {
+            case_insensitive: Some(_),
+            ..
+        })) => Some((
+            "#[string(case_insensitive)]",
+            &["PartialEq", "Eq", "PartialOrd", "Ord", "Hash"][..],
+        )),
+        Some(TypeAnnotation::Float(FloatAttr {
+            total_ord: true,
+            hash,
+            ..
+        })) => Some(match hash {
+            true => (
+                "#[float(total_ord, hash)]",
+                &["PartialEq", "Eq", "PartialOrd", "Ord", "Hash"][..],
+            ),
+            false => (
+                "#[float(total_ord)]",
+                &["PartialEq", "Eq", "PartialOrd", "Ord"][..],
+            ),
+        }),
+        _ => None,
+    };
+    if let Some((attr, traits)) = comparison_impls {
+        if let Some(path) = find_derive(&attrs, traits) {
+            return derive_conflict(&path, attr);
+        }
+    }
+
+    if let Some(one_of) = &special_attrs.one_of {
+        if special_attrs.secret.is_some() {
+            return one_of_secret(one_of.span);
+        }
+        match &special_attrs.type_annotation {
+            Some(TypeAnnotation::Int(_) | TypeAnnotation::Float(_)) => {
+                return one_of_not_string(one_of.span)
+            }
+            Some(TypeAnnotation::String(string)) if string.has_constraints() => {
+                return one_of_validated(one_of.span)
             }
+            _ => {}
+        }
+        if special_attrs.validate.is_some() {
+            return one_of_validated(one_of.span);
         }
     }
-}
 
+    if let Some(prefixed_id) = &special_attrs.prefixed_id {
+        if special_attrs.secret.is_some()
+            || special_attrs.validate.is_some()
+            || special_attrs.one_of.is_some()
+            || special_attrs.type_annotation.is_some()
+        {
+            return prefixed_id_conflict(prefixed_id.span);
+        }
+    }
+
+    if let Some(id) = &special_attrs.id {
+        let conflicts = special_attrs.secret.is_some()
+            || special_attrs.validate.is_some()
+            || special_attrs.normalize.is_some()
+            || special_attrs.one_of.is_some()
+            || special_attrs.prefixed_id.is_some();
+        match id.kind.is_int() {
+            // integer IDs get the `#[int]` formatting and `FromStr` impls, but no arithmetic
+            true => {
+                if conflicts
+                    || !matches!(
+                        special_attrs.type_annotation,
+                        Some(TypeAnnotation::Int(IntAttr { options: None, .. }))
+                    )
+                {
+                    return int_id_conflict(id.span);
+                }
+                if !id.kind.supports_inner(&inner) {
+                    return int_id_inner(inner.span(), &id.kind);
+                }
+            }
+            false if conflicts || special_attrs.type_annotation.is_some() => {
+                return id_conflict(id.span)
+            }
+            false => {}
+        }
+        // UUIDs and ULIDs use the crates that are re-exported by `microtype`, and snowflakes need
+        // the system clock
+        let feature = match id.kind {
+            IdKind::UuidV4 | IdKind::UuidV7 => Some(("uuid", HAS_UUID)),
+            IdKind::Ulid => Some(("ulid", HAS_ULID)),
+            IdKind::Snowflake(_) => Some(("std", HAS_STD)),
+            IdKind::Sequence => None,
+        };
+        if let Some((feature, false)) = feature {
+            return id_feature_missing(id.span, feature);
+        }
+        if let Some(path) = find_derive(&attrs, id.kind.generated_traits()) {
+            return derive_conflict(&path, id.display());
+        }
+    }
+
+    if let Some(checksum) = &special_attrs.checksum {
+        let conflicts = special_attrs.secret.is_some()
+            || special_attrs.validate.is_some()
+            || special_attrs.one_of.is_some()
+            || special_attrs.prefixed_id.is_some()
+            || special_attrs.id.is_some();
+        match &special_attrs.type_annotation {
+            _ if conflicts => return checksum_conflict(checksum.span),
+            Some(TypeAnnotation::String(string)) if !string.has_constraints() => {}
+            // integers are only validated, so don't get arithmetic that could change the digits
+            Some(TypeAnnotation::Int(IntAttr { options: None, .. })) => {
+                if !ChecksumAttr::supports_int(&inner) {
+                    return checksum_without_unsigned(inner.span());
+                }
+            }
+            _ => return checksum_conflict(checksum.span),
+        }
+    }
+
+    // a range, `finite`, string constraint, `#[one_of(...)]`, `#[prefixed_id(...)]` or
+    // `#[checksum(...)]` makes the microtype validated, with the check as the validator
+    let implicitly_validated = matches!(
+        special_attrs.type_annotation,
+        Some(TypeAnnotation::Int(IntAttr { range: Some(_), .. }))
+            | Some(TypeAnnotation::Float(FloatAttr { finite: true, .. }))
+    ) || matches!(
+        &special_attrs.type_annotation,
+        Some(TypeAnnotation::String(string)) if string.has_constraints()
+    ) || special_attrs.one_of.is_some()
+        || special_attrs.prefixed_id.is_some()
+        || special_attrs.checksum.is_some();
+
+    // the generated impls use `Microtype::into_inner` on the left hand side
+    if special_attrs.secret.is_some() || special_attrs.validate.is_some() || implicitly_validated {
+        if let Some(cross_op) = special_attrs
+            .cross_ops
+            .iter()
+            .find(|cross_op| cross_op.owner() == &name)
+        {
+            return cross_ops_without_microtype(cross_op.owner().span());
+        }
+    }
+
+    match (&special_attrs.secret, &special_attrs.validate) {
+        (None, None) if implicitly_validated => validated::generate_validated(
+            inner,
+            name,
+            generics,
+            vis,
+            attrs,
+            special_attrs,
+            hand_written,
+        ),
+        (None, None) => normal::generate_normal(
+            inner,
+            name,
+            generics,
+            vis,
+            attrs,
+            special_attrs,
+            hand_written,
+        ),
+        (None, Some(_)) => validated::generate_validated(
+            inner,
+            name,
+            generics,
+            vis,
+            attrs,
+            special_attrs,
+            hand_written,
+        ),
+        (Some(_), Some(validate)) => validated_secret(validate.validator.span()),
+        (Some(SecretAttr { path, .. }), None) => match (HAS_SECRET, hand_written) {
+            (false, _) => secret_feature_missing(path.span()),
+            (true, false) => {
+                secret::generate_secret(inner, name, generics, attrs, vis, special_attrs)
+            }
+            (true, true) => {
+                secret::generate_hand_written_secret(inner, name, generics, special_attrs)
+            }
+        },
+    }
+}
@@ -1,26 +1,66 @@
 use super::{
-    diesel::diesel_impl_not_secret,
-    special_attrs::{generate_int_impls, string_impls, SpecialAttrs, TypeAnnotation},
-    HAS_DEREF_IMPLS, HAS_SERDE,
+    diesel::{diesel_impl_not_secret, diesel_impl_via_str},
+    generics::{with_param, with_predicates},
+    special_attrs::{
+        cross_ops_impls, generate_float_impls, generate_int_impls, id_impls, normalize_inner,
+        normalizes, string_impls, IdKind, IntAttr, SpecialAttrs, TypeAnnotation,
+    },
+    tagged::{tagged_impls, Tag},
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Attribute, Ident, Type, Visibility};
+use syn::{parse_quote, Attribute, Generics, Ident, Type, Visibility};
+
+fn generate_struct(
+    name: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    inner: &Type,
+    private: bool,
+    tag: Option<&Tag>,
+    serde: bool,
+) -> TokenStream {
+    let field_vis = match private {
+        true => quote! {},
+        false => quote! { pub },
+    };
+    let where_clause = &generics.where_clause;
+    let tag_field = tag.map(|tag| tag.field(serde));
 
-fn generate_struct(name: &Ident, vis: &Visibility, inner: &Type) -> TokenStream {
     quote! {
         #[repr(transparent)]
-        #vis struct #name(pub #inner);
+        #vis struct #name #generics (#field_vis #inner #tag_field) #where_clause;
     }
 }
 
-fn generate_microtype_impl(name: &Ident, inner: &Type) -> TokenStream {
+fn generate_microtype_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    normalize: &TokenStream,
+    normalizes: bool,
+    tag: Option<&Tag>,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let tag_init = tag.map(Tag::init);
+    // modifying the value in place would skip normalization
+    let mut_impl = (!normalizes).then(|| {
+        quote! {
+            impl #impl_generics ::microtype::MicrotypeMut for #name #ty_generics #where_clause {
+                fn inner_mut(&mut self) -> &mut Self::Inner {
+                    &mut self.0
+                }
+            }
+        }
+    });
+
     quote! {
-        impl ::microtype::Microtype for #name {
+        impl #impl_generics ::microtype::Microtype for #name #ty_generics #where_clause {
             type Inner = #inner;
 
             fn new(inner: Self::Inner) -> Self {
-                Self(inner)
+                #normalize
+                Self(inner #tag_init)
             }
 
             fn into_inner(self) -> Self::Inner {
@@ -31,94 +71,208 @@ fn generate_microtype_impl(name: &Ident, inner: &Type) -> TokenStream {
                 &self.0
             }
 
-            fn inner_mut(&mut self) -> &mut Self::Inner {
-                &mut self.0
-            }
-
-
-            fn convert<T: ::microtype::Microtype<Inner = Self::Inner>>(self) -> T {
-                T::new(self.0)
+            fn convert<__T: ::microtype::Microtype<Inner = Self::Inner>>(self) -> __T {
+                __T::new(self.0)
             }
         }
+
+        #mut_impl
     }
 }
 
-fn generate_from_impl(name: &Ident, inner: &Type) -> TokenStream {
+fn generate_from_impl(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     quote! {
-        impl ::std::convert::From<#inner> for #name {
+        impl #impl_generics ::core::convert::From<#inner> for #name #ty_generics #where_clause {
             fn from(inner: #inner) -> Self {
-                Self(inner)
+                <Self as ::microtype::Microtype>::new(inner)
             }
         }
     }
 }
 
-fn generate_deref_impl(name: &Ident, inner: &Type) -> TokenStream {
-    if HAS_DEREF_IMPLS {
-        quote! {
-            impl ::core::ops::Deref for #name {
-                type Target = #inner;
+/// `Deref`, and `DerefMut` unless the microtype normalizes, since that could un-normalize it
+fn generate_deref_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    deref: bool,
+    normalizes: bool,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-                fn deref(&self) -> &Self::Target {
-                    &self.0
-                }
-            }
+    if !deref {
+        return quote! {};
+    }
 
-            impl ::core::ops::DerefMut for #name {
+    let deref_mut = (!normalizes).then(|| {
+        quote! {
+            impl #impl_generics ::core::ops::DerefMut for #name #ty_generics #where_clause {
                 fn deref_mut(&mut self) -> &mut Self::Target {
                     &mut self.0
                 }
             }
         }
-    } else {
-        quote! {}
+    });
+
+    quote! {
+        impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+            type Target = #inner;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        #deref_mut
     }
 }
 
-fn serde_derives() -> TokenStream {
-    if HAS_SERDE {
-        quote! {
+fn serde_derives(serde: bool, normalize: bool) -> TokenStream {
+    match (serde, normalize) {
+        (true, false) => quote! {
             #[derive(::serde::Deserialize, ::serde::Serialize)]
             #[serde(transparent)]
+        },
+        (true, true) => quote! {
+            #[derive(::serde::Serialize)]
+            #[serde(transparent)]
+        },
+        (false, _) => quote! {},
+    }
+}
+
+// serde's `transparent` would skip normalization, so this has to be written by hand
+fn deserialize_impl(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let de_generics = with_param(generics, parse_quote!('__de));
+    let de_generics = with_predicates(
+        &de_generics,
+        [parse_quote!(#inner: ::serde::Deserialize<'__de>)],
+    );
+    let (impl_generics, _, where_clause) = de_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::serde::Deserialize<'__de> for #name #ty_generics #where_clause {
+            fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> Result<Self, __D::Error> {
+                let inner = <#inner as ::serde::Deserialize<'__de>>::deserialize(deserializer)?;
+                Ok(<Self as ::microtype::Microtype>::new(inner))
+            }
         }
-    } else {
-        quote! {}
     }
 }
 
 pub fn generate_normal(
     inner: Type,
     name: Ident,
+    generics: Generics,
     vis: Visibility,
     attrs: Vec<Attribute>,
     special_attrs: SpecialAttrs,
+    hand_written: bool,
 ) -> TokenStream {
-    let struct_def = generate_struct(&name, &vis, &inner);
-    let microtype_impl = generate_microtype_impl(&name, &inner);
-    let from_impl = generate_from_impl(&name, &inner);
-    let deref_impl = generate_deref_impl(&name, &inner);
-    let serde_attrs = serde_derives();
-
-    let diesel_impls = special_attrs
-        .diesel_type
-        .map(|sql_type| diesel_impl_not_secret(&sql_type, &inner, &name));
-
-    let type_specific_impls = match special_attrs.type_annotation {
-        None => quote! {},
-        Some(TypeAnnotation::String) => string_impls(&name, &inner),
-        Some(TypeAnnotation::Int) => generate_int_impls(&name, &inner),
+    let serde = special_attrs.overrides.serde();
+    let normalize = normalize_inner(&special_attrs);
+    let normalizes = normalizes(&special_attrs);
+    let tag = special_attrs.tagged.and_then(|_| Tag::find(&generics));
+    let struct_def = generate_struct(
+        &name,
+        &generics,
+        &vis,
+        &inner,
+        // a public field could be used to skip normalization
+        special_attrs.private || normalizes,
+        tag.as_ref(),
+        serde,
+    );
+    let microtype_impl = generate_microtype_impl(
+        &name,
+        &generics,
+        &inner,
+        &normalize,
+        normalizes,
+        tag.as_ref(),
+    );
+    let from_impl = generate_from_impl(&name, &generics, &inner);
+    let deref_impl = generate_deref_impl(
+        &name,
+        &generics,
+        &inner,
+        special_attrs.overrides.deref(),
+        normalizes,
+    );
+    // UUIDs, ULIDs and publicly encoded ints are always serialized as strings, so they have their
+    // own impls
+    let string_id = special_attrs
+        .id
+        .as_ref()
+        .is_some_and(|id| !id.kind.is_int());
+    let public_encoding = matches!(
+        special_attrs.type_annotation,
+        Some(TypeAnnotation::Int(IntAttr {
+            public_encoding: Some(_),
+            ..
+        }))
+    );
+    let serde_attrs = serde_derives(serde && !string_id && !public_encoding, normalizes);
+    let deserialize_impl =
+        (serde && normalizes && !hand_written).then(|| deserialize_impl(&name, &generics, &inner));
+    let tagged_impls = tag
+        .as_ref()
+        .map(|tag| tagged_impls(&name, &generics, &inner, tag));
+
+    // `ulid` doesn't support diesel, so ULIDs are stored as text
+    let diesel_impls = special_attrs.diesel_type.map(|sql_type| {
+        match special_attrs.id.as_ref().map(|id| &id.kind) {
+            Some(IdKind::Ulid) => diesel_impl_via_str(&sql_type, &inner, &name, &generics),
+            _ => diesel_impl_not_secret(&sql_type, &inner, &name, &generics),
+        }
+    });
+
+    let cross_ops = &special_attrs.cross_ops;
+    let replaced_ops: Vec<_> = cross_ops
+        .iter()
+        .filter_map(|cross_op| cross_op.replaced_op(&name))
+        .collect();
+    let cross_ops_impls = cross_ops_impls(&name, &generics, cross_ops);
+
+    let type_specific_impls = match (&special_attrs.type_annotation, &special_attrs.id) {
+        // integer IDs replace the `#[int]` impls
+        (_, Some(id)) => id_impls(&name, &generics, &inner, id, serde),
+        (None, None) => quote! {},
+        (Some(TypeAnnotation::String(string)), None) => {
+            string_impls(&name, &generics, &inner, string)
+        }
+        (Some(TypeAnnotation::Int(int)), None) => {
+            generate_int_impls(&name, &generics, &inner, int, &replaced_ops, serde)
+        }
+        (Some(TypeAnnotation::Float(float)), None) => {
+            generate_float_impls(&name, &generics, &inner, float, &replaced_ops)
+        }
+    };
+
+    // a hand-written struct already has its attributes
+    let struct_def = match hand_written {
+        true => quote! {},
+        false => quote! {
+            #(#attrs)*
+            #serde_attrs
+            #struct_def
+        },
     };
 
     quote! {
-        #(#attrs)*
-        #serde_attrs
         #struct_def
 
         #microtype_impl
 
         #from_impl
         #deref_impl
+        #deserialize_impl
+        #tagged_impls
         #type_specific_impls
+        #cross_ops_impls
         #diesel_impls
     }
 }
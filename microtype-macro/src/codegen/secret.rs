@@ -1,35 +1,50 @@
 use crate::codegen::{
     diesel::diesel_impl_secret,
-    special_attrs::{secret_string_impls, TypeAnnotation},
+    errors::hand_written_secret_field,
+    generics::{with_predicates, without_param},
+    special_attrs::{secret_float_impls, secret_int_impls, secret_string_impls, TypeAnnotation},
+    tagged::{clone_impl, debug_impl, Tag},
 };
 
-use super::{special_attrs::SpecialAttrs, HAS_SERDE, HAS_TEST_IMPLS};
+use super::special_attrs::SpecialAttrs;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Attribute, Ident, Type, Visibility};
+use syn::{
+    parse_quote, spanned::Spanned, Attribute, GenericArgument, Generics, Ident, LitStr,
+    PathArguments, Type, TypePath, Visibility,
+};
 
-fn attrs_for_both(serialize: bool) -> TokenStream {
+/// For tagged microtypes, `Clone` and `Debug` are implemented by hand instead, so that the tag
+/// doesn't need to implement them
+fn attrs_for_both(serialize: bool, serde: bool, test_impls: bool, tagged: bool) -> TokenStream {
     let mut attrs = quote! {
         #[repr(transparent)]
-        #[derive(::std::clone::Clone)]
-        #[cfg_attr(not(test), derive(::std::fmt::Debug))]
     };
 
+    if !tagged {
+        attrs.extend(quote! {
+            #[derive(::core::clone::Clone)]
+            #[cfg_attr(not(test), derive(::core::fmt::Debug))]
+        });
+    }
+
     // without this feature, we just derive debug in test builds as well
-    if !HAS_TEST_IMPLS {
+    if !test_impls && !tagged {
         attrs.extend(quote! {
-            #[cfg_attr(test, derive(::std::fmt::Debug))]
+            #[cfg_attr(test, derive(::core::fmt::Debug))]
         });
     }
 
-    if HAS_SERDE {
+    if serde {
         attrs.extend(match serialize {
             false => quote! {
                 #[derive(::serde::Deserialize)]
+                #[serde(transparent)]
             },
             true => quote! {
                 #[derive(::serde::Deserialize)]
                 #[derive(::serde::Serialize)]
+                #[serde(transparent)]
             },
         })
     }
@@ -37,49 +52,141 @@ fn attrs_for_both(serialize: bool) -> TokenStream {
     attrs
 }
 
-fn test_impls(name: &Ident) -> TokenStream {
+/// `Secret<T>` only implements `Deserialize` when `T: DeserializeOwned`, but serde infers a
+/// `Deserialize<'de>` bound for each type parameter, which isn't enough
+fn serde_bound(wrapper_ty: &Type, wrapper_generics: &Generics, serde: bool) -> TokenStream {
+    if !serde || wrapper_generics.params.is_empty() {
+        return quote! {};
+    }
+
+    let bound = quote! {
+        #wrapper_ty: ::serde::de::DeserializeOwned + ::core::clone::Clone
+    };
+    let bound = LitStr::new(&bound.to_string(), wrapper_ty.span());
+
+    quote! {
+        #[serde(bound(deserialize = #bound))]
+    }
+}
+
+/// `#[secret(hash)]` already implements `PartialEq`, so it isn't implemented again for tests
+fn test_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    test_impls: bool,
+    hash: bool,
+) -> TokenStream {
+    // without test impls, `Debug` is derived in test builds instead (see `attrs_for_both`)
+    if !test_impls {
+        return quote! {};
+    }
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let debug_generics = with_predicates(generics, [parse_quote!(#inner: ::core::fmt::Debug)]);
+    let (debug_impl_generics, _, debug_where_clause) = debug_generics.split_for_impl();
+    let eq_generics = with_predicates(generics, [parse_quote!(#inner: ::core::cmp::PartialEq)]);
+    let (eq_impl_generics, _, eq_where_clause) = eq_generics.split_for_impl();
+
+    let eq_impl = match hash {
+        true => quote! {},
+        false => quote! {
+            #[cfg(test)]
+            impl #eq_impl_generics ::core::cmp::PartialEq for #name #ty_generics #eq_where_clause {
+                fn eq(&self, other: &Self) -> bool {
+                    use ::microtype::secrecy::ExposeSecret;
+                    self.expose_secret().eq(other.expose_secret())
+                }
+            }
+        },
+    };
+
     quote! {
         #[cfg(test)]
-        impl ::std::fmt::Debug for #name {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        impl #debug_impl_generics ::core::fmt::Debug for #name #ty_generics #debug_where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 use ::microtype::secrecy::ExposeSecret;
-                f.write_str(self.expose_secret())
+                ::core::fmt::Debug::fmt(self.expose_secret(), f)
             }
         }
 
-        #[cfg(test)]
-        impl ::std::cmp::PartialEq for #name {
+        #eq_impl
+    }
+}
+
+/// `PartialEq` and `Eq` for `#[secret(hash)]`, using a constant-time comparison, and `Hash`, which
+/// only passes a keyed hash of the secret on to the caller's `Hasher`
+///
+/// `Hash` relies on the inner type's `Hash` being consistent with its `ConstantTimeEq`, which is
+/// true for the impls in `microtype`
+fn hash_impls(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let eq_generics = with_predicates(
+        generics,
+        [parse_quote!(#inner: ::microtype::ConstantTimeEq)],
+    );
+    let (eq_impl_generics, _, eq_where_clause) = eq_generics.split_for_impl();
+    let hash_generics = with_predicates(generics, [parse_quote!(#inner: ::core::hash::Hash)]);
+    let (hash_impl_generics, _, hash_where_clause) = hash_generics.split_for_impl();
+    let expose =
+        |value| quote!(<Self as ::microtype::secrecy::ExposeSecret<#inner>>::expose_secret(#value));
+    let (lhs, rhs) = (expose(quote!(self)), expose(quote!(other)));
+
+    quote! {
+        impl #eq_impl_generics ::core::cmp::PartialEq for #name #ty_generics #eq_where_clause {
             fn eq(&self, other: &Self) -> bool {
-                use ::microtype::secrecy::ExposeSecret;
-                self.expose_secret().eq(other.expose_secret())
+                ::microtype::ConstantTimeEq::ct_eq(#lhs, #rhs)
+            }
+        }
+
+        impl #eq_impl_generics ::core::cmp::Eq for #name #ty_generics #eq_where_clause {}
+
+        impl #hash_impl_generics ::core::hash::Hash for #name #ty_generics #hash_where_clause {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                ::core::hash::Hasher::write_u64(state, ::microtype::__private::keyed_hash(#lhs));
             }
         }
     }
 }
 
-fn wrapper_impls(serialize: bool, wrapper: &Ident) -> TokenStream {
+fn wrapper_impls(
+    serialize: bool,
+    serde: bool,
+    wrapper: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let cloneable_generics = with_predicates(generics, [parse_quote!(Self: ::core::clone::Clone)]);
+    let cloneable_where_clause = &cloneable_generics.where_clause;
+
     let mut tokens = quote! {
-        impl ::microtype::secrecy::CloneableSecret for #wrapper {}
-        impl ::microtype::secrecy::DebugSecret for #wrapper {}
-        impl ::microtype::secrecy::Zeroize for #wrapper {
+        impl #impl_generics ::microtype::secrecy::CloneableSecret for #wrapper #ty_generics #cloneable_where_clause {}
+        impl #impl_generics ::microtype::secrecy::DebugSecret for #wrapper #ty_generics #where_clause {}
+        impl #impl_generics ::microtype::secrecy::Zeroize for #wrapper #ty_generics #where_clause {
             fn zeroize(&mut self) {
                 ::microtype::secrecy::Zeroize::zeroize(&mut self.0)
             }
         }
     };
 
-    if serialize && HAS_SERDE {
+    if serialize && serde {
+        let serializable_generics =
+            with_predicates(generics, [parse_quote!(Self: ::serde::Serialize)]);
+        let serializable_where_clause = &serializable_generics.where_clause;
+
         tokens.extend(quote! {
-            impl ::microtype::secrecy::SerializableSecret for #wrapper {}
+            impl #impl_generics ::microtype::secrecy::SerializableSecret for #wrapper #ty_generics #serializable_where_clause {}
         });
     }
 
     tokens
 }
 
-fn expose_secret_impl(name: &Ident, inner: &Type) -> TokenStream {
+fn expose_secret_impl(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     quote! {
-        impl ::microtype::secrecy::ExposeSecret<#inner> for #name {
+        impl #impl_generics ::microtype::secrecy::ExposeSecret<#inner> for #name #ty_generics #where_clause {
             fn expose_secret(&self) -> &#inner {
                 use ::microtype::secrecy::ExposeSecret;
                 &self.0.expose_secret().0
@@ -88,43 +195,63 @@ fn expose_secret_impl(name: &Ident, inner: &Type) -> TokenStream {
     }
 }
 
-fn secret_microtype_impl(name: &Ident, wrapper: &Ident, inner: &Type) -> TokenStream {
+fn secret_microtype_impl(
+    name: &Ident,
+    generics: &Generics,
+    wrapper: &Ident,
+    inner: &Type,
+    tag: Option<&Tag>,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let tag_init = tag.map(Tag::init);
+
     quote! {
-        impl ::microtype::SecretMicrotype for #name {
+        impl #impl_generics ::microtype::SecretMicrotype for #name #ty_generics #where_clause {
             type Inner = #inner;
 
             fn new(inner: Self::Inner) -> Self {
-                Self(::microtype::secrecy::Secret::new(#wrapper(inner)))
+                Self(::microtype::secrecy::Secret::new(#wrapper(inner)) #tag_init)
             }
         }
     }
 }
 
-fn generate_structs(
+fn generate_struct(
     name: &Ident,
-    inner: &Type,
+    generics: &Generics,
     vis: &Visibility,
     extra_attrs: &[Attribute],
-    serialize: bool,
-) -> (TokenStream, Ident) {
-    let wrapper = Ident::new(&format!("__Wrapper{}", name), name.span());
-    let attrs_for_both = attrs_for_both(serialize);
+    attrs: &TokenStream,
+    fields: &TokenStream,
+) -> TokenStream {
+    let where_clause = &generics.where_clause;
 
-    let tokens = quote! {
+    quote! {
         #(#extra_attrs)*
-        #attrs_for_both
-        #vis struct #name(::microtype::secrecy::Secret<#wrapper>);
+        #attrs
+        #vis struct #name #generics (#fields) #where_clause;
+    }
+}
 
-        #attrs_for_both
-        struct #wrapper(#inner);
-    };
+/// The wrapper never has the tag, since it doesn't need a `PhantomData` field of its own
+fn generate_wrapper_struct(
+    wrapper: &Ident,
+    wrapper_generics: &Generics,
+    inner: &Type,
+    attrs: &TokenStream,
+) -> TokenStream {
+    let where_clause = &wrapper_generics.where_clause;
 
-    (tokens, wrapper)
+    quote! {
+        #attrs
+        struct #wrapper #wrapper_generics (#inner) #where_clause;
+    }
 }
 
 pub fn generate_secret(
     inner: Type,
     name: Ident,
+    generics: Generics,
     extra_attrs: Vec<Attribute>,
     vis: Visibility,
     special_attrs: SpecialAttrs,
@@ -135,30 +262,176 @@ pub fn generate_secret(
     );
     let secret = special_attrs.secret.unwrap();
     let serialize = secret.serialize.is_some();
+    let hash = secret.hash.is_some();
+    let serde = special_attrs.overrides.serde();
+    let test_impls = special_attrs.overrides.test_impls();
+    let tag = special_attrs.tagged.and_then(|_| Tag::find(&generics));
+
+    // `Secret<T>` requires `T: Zeroize`, and the wrapper is only `Zeroize` if the inner type is
+    let generics = with_predicates(
+        &generics,
+        [parse_quote!(#inner: ::microtype::secrecy::Zeroize)],
+    );
+    let wrapper = Ident::new(&format!("__Wrapper{}", name), name.span());
+    let wrapper_generics = match &tag {
+        Some(tag) => without_param(&generics, tag.param()),
+        None => generics.clone(),
+    };
+    let (_, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
+    let wrapper_ty: Type = parse_quote!(#wrapper #wrapper_ty_generics);
+    let secret_ty: Type = parse_quote!(::microtype::secrecy::Secret<#wrapper_ty>);
+
+    let mut attrs = attrs_for_both(serialize, serde, test_impls, tag.is_some());
+    attrs.extend(serde_bound(&wrapper_ty, &wrapper_generics, serde));
+    let tag_field = tag.as_ref().map(|tag| tag.field(serde));
+    let struct_def = generate_struct(
+        &name,
+        &generics,
+        &vis,
+        &extra_attrs,
+        &attrs,
+        &quote!(#secret_ty #tag_field),
+    );
+    let wrapper_attrs = attrs_for_both(serialize, serde, test_impls, false);
+    let wrapper_struct_def =
+        generate_wrapper_struct(&wrapper, &wrapper_generics, &inner, &wrapper_attrs);
+
+    let wrapper_impls = wrapper_impls(serialize, serde, &wrapper, &wrapper_generics);
+    let tagged_impls = tag.as_ref().map(|tag| {
+        let clone_impl = clone_impl(&name, &generics, &secret_ty, tag);
+        let debug_impl = debug_impl(&name, &generics, &secret_ty);
+        // with test impls, `Debug` exposes the secret in test builds
+        let debug_cfg = match test_impls {
+            true => quote! { #[cfg(not(test))] },
+            false => quote! {},
+        };
 
-    let (struct_defs, wrapper) = generate_structs(&name, &inner, &vis, &extra_attrs, serialize);
-    let wrapper_impls = wrapper_impls(serialize, &wrapper);
-    let test_impls = test_impls(&name);
-    let expose_secret_impl = expose_secret_impl(&name, &inner);
-    let secret_microtype_impl = secret_microtype_impl(&name, &wrapper, &inner);
+        quote! {
+            #clone_impl
+            #debug_cfg
+            #debug_impl
+        }
+    });
+    let test_impls = self::test_impls(&name, &generics, &inner, test_impls, hash);
+    let hash_impls = hash.then(|| hash_impls(&name, &generics, &inner));
+    let expose_secret_impl = expose_secret_impl(&name, &generics, &inner);
+    let secret_microtype_impl =
+        secret_microtype_impl(&name, &generics, &wrapper, &inner, tag.as_ref());
     let diesel_impls = special_attrs
         .diesel_type
-        .map(|sql_type| diesel_impl_secret(&sql_type, &inner, &name));
+        .map(|sql_type| diesel_impl_secret(&sql_type, &inner, &name, &generics));
 
     let type_specific_impls = match special_attrs.type_annotation {
         None => quote! {},
-        Some(TypeAnnotation::String) => secret_string_impls(&name),
-        Some(TypeAnnotation::Int) => todo!(),
+        Some(TypeAnnotation::String(_)) => secret_string_impls(&name, &generics),
+        Some(TypeAnnotation::Int(_)) => secret_int_impls(&name, &generics, &inner),
+        Some(TypeAnnotation::Float(_)) => secret_float_impls(&name, &generics, &inner),
     };
 
     quote! {
-        #struct_defs
+        #struct_def
+        #wrapper_struct_def
 
         #wrapper_impls
+        #tagged_impls
         #expose_secret_impl
         #secret_microtype_impl
         #test_impls
+        #hash_impls
         #type_specific_impls
         #diesel_impls
     }
 }
+
+/// Get `T` from a `Secret<T>` field
+fn secret_inner(field: &Type) -> Option<&Type> {
+    let segment = match field {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Secret" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A hand-written secret has a `Secret<T>` field rather than a wrapper, and it's up to the user to
+/// derive `Debug`, `Clone`, etc., so test impls aren't generated either
+pub fn generate_hand_written_secret(
+    field: Type,
+    name: Ident,
+    generics: Generics,
+    special_attrs: SpecialAttrs,
+) -> TokenStream {
+    let inner = match secret_inner(&field) {
+        Some(inner) => inner,
+        None => return hand_written_secret_field(field.span()),
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let hash_impls = special_attrs
+        .secret
+        .as_ref()
+        .and_then(|secret| secret.hash.as_ref())
+        .map(|_| hash_impls(&name, &generics, inner));
+    let diesel_impls = special_attrs
+        .diesel_type
+        .map(|sql_type| diesel_impl_secret(&sql_type, inner, &name, &generics));
+
+    let type_specific_impls = match special_attrs.type_annotation {
+        None => quote! {},
+        Some(TypeAnnotation::String(_)) => secret_string_impls(&name, &generics),
+        Some(TypeAnnotation::Int(_)) => secret_int_impls(&name, &generics, inner),
+        Some(TypeAnnotation::Float(_)) => secret_float_impls(&name, &generics, inner),
+    };
+
+    quote! {
+        impl #impl_generics ::microtype::secrecy::ExposeSecret<#inner> for #name #ty_generics #where_clause {
+            fn expose_secret(&self) -> &#inner {
+                <#field as ::microtype::secrecy::ExposeSecret<#inner>>::expose_secret(&self.0)
+            }
+        }
+
+        impl #impl_generics ::microtype::SecretMicrotype for #name #ty_generics #where_clause {
+            type Inner = #inner;
+
+            fn new(inner: Self::Inner) -> Self {
+                Self(::microtype::secrecy::Secret::new(inner))
+            }
+        }
+
+        #hash_impls
+        #type_specific_impls
+        #diesel_impls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    #[test]
+    fn finds_secret_inner() {
+        let field: Type = parse_quote!(::microtype::secrecy::Secret<Vec<u8>>);
+        let inner = secret_inner(&field).unwrap();
+
+        assert_eq!(inner.to_token_stream().to_string(), "Vec < u8 >");
+    }
+
+    #[test]
+    fn no_secret_inner() {
+        assert!(secret_inner(&parse_quote!(String)).is_none());
+        assert!(secret_inner(&parse_quote!(Secret)).is_none());
+        assert!(secret_inner(&parse_quote!(&Secret<String>)).is_none());
+    }
+}
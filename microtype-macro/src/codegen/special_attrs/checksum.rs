@@ -0,0 +1,211 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Attribute, Generics, Ident, Type};
+
+use super::helpers::is_one_of;
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[checksum(luhn)]` or `#[checksum(iso7064_mod97)]`"))
+}
+
+fn duplicate_checksum(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `checksum` attribute found"))
+}
+
+/// A check digit algorithm, mirroring `microtype::ChecksumAlgorithm`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Luhn,
+    Iso7064Mod97,
+}
+
+impl ChecksumAlgorithm {
+    fn variant(self) -> TokenStream {
+        match self {
+            Self::Luhn => quote!(::microtype::ChecksumAlgorithm::Luhn),
+            Self::Iso7064Mod97 => quote!(::microtype::ChecksumAlgorithm::Iso7064Mod97),
+        }
+    }
+}
+
+/// The contents of a `#[checksum(...)]` attribute
+#[derive(Debug)]
+pub struct ChecksumAttr {
+    pub algorithm: ChecksumAlgorithm,
+    /// The span of `checksum`
+    pub span: Span,
+}
+
+impl ChecksumAttr {
+    /// Integers are checked using their decimal digits, which only makes sense if they can't be
+    /// negative
+    pub fn supports_int(inner: &Type) -> bool {
+        is_one_of(inner, &["u8", "u16", "u32", "u64", "u128", "usize"])
+    }
+}
+
+pub fn strip_checksum_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<ChecksumAttr>), TokenStream> {
+    let (checksum, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("checksum"));
+
+    let attr = match &checksum[..] {
+        [] => return Ok((attrs, None)),
+        [single] => single,
+        [_, second, ..] => return Err(duplicate_checksum(second.span())),
+    };
+
+    let algorithm = match attr.parse_args::<Ident>() {
+        Ok(ident) if ident == "luhn" => ChecksumAlgorithm::Luhn,
+        Ok(ident) if ident == "iso7064_mod97" => ChecksumAlgorithm::Iso7064Mod97,
+        Ok(ident) => return Err(generic_err(ident.span())),
+        Err(e) => return Err(generic_err(e.span())),
+    };
+
+    let checksum = ChecksumAttr {
+        algorithm,
+        span: attr.path.span(),
+    };
+
+    Ok((attrs, Some(checksum)))
+}
+
+/// The inner value as something the runtime functions accept
+fn checked_value(int: bool) -> TokenStream {
+    match int {
+        true => quote!(inner as ::core::primitive::u128),
+        false => quote!(&inner),
+    }
+}
+
+fn error(name: &Ident, checksum: &ChecksumAttr, kind: TokenStream) -> TokenStream {
+    let microtype = name.to_string();
+    let algorithm = checksum.algorithm.variant();
+
+    quote! {
+        ::microtype::ChecksumError {
+            microtype: #microtype,
+            algorithm: #algorithm,
+            kind: #kind,
+        }
+    }
+}
+
+/// Statements for `ValidatedMicrotype::try_new` that check the check digits
+pub fn checksum_check(name: &Ident, checksum: &ChecksumAttr, int: bool) -> TokenStream {
+    let algorithm = checksum.algorithm.variant();
+    let validate = match int {
+        true => quote!(::microtype::__private::checksum::validate_int),
+        false => quote!(::microtype::__private::checksum::validate_str),
+    };
+    let value = checked_value(int);
+    let error = error(name, checksum, quote!(kind));
+
+    quote! {
+        if let Err(kind) = #validate(#algorithm, #value) {
+            return Err(#error);
+        }
+    }
+}
+
+/// `generate_with_checksum`, which appends the check digits to a payload
+///
+/// The result still goes through `try_new`, so it is normalized like any other value
+pub fn checksum_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    checksum: &ChecksumAttr,
+    int: bool,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let algorithm = checksum.algorithm.variant();
+    let overflow = error(
+        name,
+        checksum,
+        quote!(::microtype::ChecksumErrorKind::Overflow),
+    );
+    let invalid = error(name, checksum, quote!(kind));
+
+    let (payload, body) = match int {
+        true => (
+            quote!(#inner),
+            quote! {
+                let inner = ::microtype::__private::checksum::with_check_digits(
+                    #algorithm,
+                    payload as ::core::primitive::u128,
+                )
+                .and_then(|inner| <#inner as ::core::convert::TryFrom<::core::primitive::u128>>::try_from(inner).ok())
+                .ok_or(#overflow)?;
+            },
+        ),
+        false => (
+            quote!(&::core::primitive::str),
+            quote! {
+                let digits = ::microtype::__private::checksum::str_check_digits(#algorithm, payload)
+                    .map_err(|kind| #invalid)?;
+                let mut inner = ::microtype::__private::alloc::string::String::from(payload);
+                inner.push_str(digits.as_str());
+            },
+        ),
+    };
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Create a value from a payload, by appending its check digits
+            pub fn generate_with_checksum(payload: #payload) -> Result<Self, ::microtype::ChecksumError> {
+                #body
+                <Self as ::microtype::ValidatedMicrotype>::try_new(inner)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    fn strip(s: &str) -> Result<Option<ChecksumAttr>, TokenStream> {
+        let microtype: MicrotypeMacro = parse_str(s).unwrap();
+        strip_checksum_attr(microtype.0[0].attrs.clone()).map(|(_, checksum)| checksum)
+    }
+
+    #[test]
+    fn strips_checksum_attr() {
+        let checksum = strip("#[checksum(luhn)] #[int] u64 { CardNumber }")
+            .unwrap()
+            .unwrap();
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Luhn);
+
+        let checksum = strip("#[checksum(iso7064_mod97)] #[string] String { Lei }")
+            .unwrap()
+            .unwrap();
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Iso7064Mod97);
+
+        assert!(strip("#[int] u64 { CardNumber }").unwrap().is_none());
+
+        for s in [
+            "#[checksum] #[int] u64 { CardNumber }",
+            "#[checksum(crc)] #[int] u64 { CardNumber }",
+            r#"#[checksum("luhn")] #[int] u64 { CardNumber }"#,
+            "#[checksum(luhn, iso7064_mod97)] #[int] u64 { CardNumber }",
+            "#[checksum(luhn)] #[checksum(luhn)] #[int] u64 { CardNumber }",
+        ] {
+            strip(s).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn only_unsigned_ints() {
+        assert!(ChecksumAttr::supports_int(&parse_str("u64").unwrap()));
+        assert!(ChecksumAttr::supports_int(&parse_str("usize").unwrap()));
+        assert!(!ChecksumAttr::supports_int(&parse_str("i64").unwrap()));
+        assert!(!ChecksumAttr::supports_int(&parse_str("String").unwrap()));
+    }
+}
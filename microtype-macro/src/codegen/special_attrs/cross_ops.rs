@@ -0,0 +1,304 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Generics, Ident, Token, Type,
+};
+
+use crate::model::Microtype;
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[ops(A op B = C, ...)]`, where `op` is one of `+`, `-`, `*`, `/`, `%`, or a conversion like `A -> B * factor` or `A -> B / factor`"))
+}
+
+fn unowned(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("the left hand side of an `#[ops(...)]` entry must be a microtype that the attribute is applied to"))
+}
+
+/// An arithmetic operator in `#[ops(...)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Operator {
+    /// The trait and method, e.g. `("Mul", "mul")`
+    pub fn names(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Add => ("Add", "add"),
+            Self::Sub => ("Sub", "sub"),
+            Self::Mul => ("Mul", "mul"),
+            Self::Div => ("Div", "div"),
+            Self::Rem => ("Rem", "rem"),
+        }
+    }
+
+    fn idents(self, span: Span) -> (Ident, Ident) {
+        let (op, method) = self.names();
+        (Ident::new(op, span), Ident::new(method, span))
+    }
+}
+
+impl Parse for Operator {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![+]) {
+            input.parse::<Token![+]>().map(|_| Self::Add)
+        } else if lookahead.peek(Token![-]) {
+            input.parse::<Token![-]>().map(|_| Self::Sub)
+        } else if lookahead.peek(Token![*]) {
+            input.parse::<Token![*]>().map(|_| Self::Mul)
+        } else if lookahead.peek(Token![/]) {
+            input.parse::<Token![/]>().map(|_| Self::Div)
+        } else if lookahead.peek(Token![%]) {
+            input.parse::<Token![%]>().map(|_| Self::Rem)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// A single entry in `#[ops(...)]`
+///
+/// The impls are generated alongside the microtype on the left hand side, so an attribute on a
+/// block only generates each entry once
+#[derive(Debug)]
+pub enum CrossOp {
+    /// `Lhs op Rhs = Output`
+    Op {
+        lhs: Ident,
+        op: Operator,
+        rhs: Type,
+        output: Type,
+    },
+    /// `From -> To * factor` or `From -> To / factor`, which implements `From<From> for To`
+    ///
+    /// The factor is kept as tokens, so it can be any expression of the inner type
+    Conversion {
+        from: Ident,
+        to: Type,
+        op: Operator,
+        factor: TokenStream,
+    },
+}
+
+impl CrossOp {
+    /// The microtype that the impls are generated alongside
+    pub fn owner(&self) -> &Ident {
+        match self {
+            Self::Op { lhs, .. } => lhs,
+            Self::Conversion { from, .. } => from,
+        }
+    }
+
+    /// The trait `name` implements for itself (e.g. `Mul` for `Meters * Meters = SquareMeters`),
+    /// which replaces the impl from `#[int]` or `#[float]`
+    pub fn replaced_op(&self, name: &Ident) -> Option<&'static str> {
+        match self {
+            Self::Op {
+                lhs,
+                op,
+                rhs: Type::Path(rhs),
+                ..
+            } if lhs == name && rhs.qself.is_none() && rhs.path.is_ident(name) => {
+                Some(op.names().0)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Parse for CrossOp {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lhs: Ident = input.parse()?;
+
+        if !input.peek(Token![->]) {
+            let op = input.parse()?;
+            let rhs = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let output = input.parse()?;
+            return Ok(Self::Op {
+                lhs,
+                op,
+                rhs,
+                output,
+            });
+        }
+
+        let _: Token![->] = input.parse()?;
+        let to = input.parse()?;
+        let op = input.parse()?;
+        if !matches!(op, Operator::Mul | Operator::Div) {
+            return Err(input.error("conversions must be `A -> B * factor` or `A -> B / factor`"));
+        }
+
+        let mut factor = TokenStream::new();
+        while !input.is_empty() && !input.peek(Token![,]) {
+            factor.extend([input.parse::<TokenTree>()?]);
+        }
+        if factor.is_empty() {
+            return Err(input.error("expected a conversion factor"));
+        }
+
+        Ok(Self::Conversion {
+            from: lhs,
+            to,
+            op,
+            factor,
+        })
+    }
+}
+
+fn parse_cross_ops(attr: &Attribute) -> Result<Vec<CrossOp>, TokenStream> {
+    match attr.parse_args_with(Punctuated::<CrossOp, Token![,]>::parse_terminated) {
+        Ok(ops) => Ok(ops.into_iter().collect()),
+        Err(e) => Err(generic_err(e.span())),
+    }
+}
+
+/// Unlike most special attributes, `#[ops(...)]` can appear more than once, e.g. on a block and on
+/// a single microtype in it
+pub fn strip_cross_ops_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Vec<CrossOp>), TokenStream> {
+    let (cross_ops, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("ops"));
+
+    let mut result = vec![];
+    for attr in &cross_ops {
+        result.extend(parse_cross_ops(attr)?);
+    }
+
+    Ok((attrs, result))
+}
+
+/// Each entry is only generated alongside its owner, so an entry whose owner doesn't have the
+/// attribute would be silently ignored
+///
+/// Parse errors are ignored here, they are reported when the attributes are stripped
+pub fn find_unowned_cross_op(microtypes: &[Microtype]) -> Option<TokenStream> {
+    for microtype in microtypes {
+        for attr in microtype
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("ops"))
+        {
+            for cross_op in parse_cross_ops(attr).unwrap_or_default() {
+                let owner = cross_op.owner();
+                let owned = microtypes
+                    .iter()
+                    .any(|other| &other.name == owner && other.attrs.contains(attr));
+
+                if !owned {
+                    return Some(unowned(owner.span()));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// The impls for the entries owned by `name`
+pub fn cross_ops_impls(name: &Ident, generics: &Generics, cross_ops: &[CrossOp]) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let into_inner = quote!(::microtype::Microtype::into_inner);
+    let mut tokens = TokenStream::new();
+
+    for cross_op in cross_ops.iter().filter(|cross_op| cross_op.owner() == name) {
+        tokens.extend(match cross_op {
+            CrossOp::Op {
+                op, rhs, output, ..
+            } => {
+                let (op, method) = op.idents(name.span());
+                quote! {
+                    impl #impl_generics ::core::ops::#op<#rhs> for #name #ty_generics #where_clause {
+                        type Output = #output;
+                        fn #method(self, rhs: #rhs) -> Self::Output {
+                            <#output as ::microtype::Microtype>::new(::core::ops::#op::#method(
+                                #into_inner(self),
+                                #into_inner(rhs),
+                            ))
+                        }
+                    }
+                }
+            }
+            CrossOp::Conversion { to, op, factor, .. } => {
+                let (op, method) = op.idents(name.span());
+                quote! {
+                    impl #impl_generics ::core::convert::From<#name #ty_generics> for #to #where_clause {
+                        fn from(value: #name #ty_generics) -> Self {
+                            <Self as ::microtype::Microtype>::new(::core::ops::#op::#method(
+                                #into_inner(value),
+                                #factor,
+                            ))
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, parse_str};
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    fn strip(s: &str) -> Result<Vec<CrossOp>, TokenStream> {
+        let microtype: MicrotypeMacro = parse_str(s).unwrap();
+        strip_cross_ops_attr(microtype.0[0].attrs.clone()).map(|(_, cross_ops)| cross_ops)
+    }
+
+    #[test]
+    fn parses_cross_ops() {
+        let cross_ops = strip(
+            "#[ops(Meters / Seconds = MetersPerSecond, Meters * Meters = SquareMeters)] \
+             #[ops(Kilometers -> Meters * 1000.0)] \
+             #[derive(Debug)] \
+             f64 { Meters, Kilometers }",
+        )
+        .unwrap();
+
+        assert_eq!(cross_ops.len(), 3);
+        assert!(matches!(
+            &cross_ops[0],
+            CrossOp::Op { lhs, op: Operator::Div, .. } if lhs == "Meters"
+        ));
+        assert!(matches!(
+            &cross_ops[2],
+            CrossOp::Conversion { from, op: Operator::Mul, factor, .. }
+                if from == "Kilometers" && factor.to_string() == "1000.0"
+        ));
+
+        let meters = parse_quote!(Meters);
+        assert_eq!(cross_ops[0].replaced_op(&meters), None);
+        assert_eq!(cross_ops[1].replaced_op(&meters), Some("Mul"));
+        assert_eq!(cross_ops[2].replaced_op(&meters), None);
+    }
+
+    #[test]
+    fn fails_on_invalid_cross_ops() {
+        for s in [
+            "Meters / Seconds",
+            "Meters & Seconds = Bits",
+            "Kilometers -> Meters",
+            "Kilometers -> Meters + 1",
+            "Kilometers -> Meters *",
+            "Meters",
+        ] {
+            strip(&format!("#[ops({s})] f64 {{ Meters }}")).unwrap_err();
+        }
+    }
+}
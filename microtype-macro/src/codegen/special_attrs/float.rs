@@ -0,0 +1,179 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, parse_str, Generics, Ident, Type};
+
+use crate::codegen::generics::with_predicates;
+
+use super::{helpers::fmt_impl, ops::float_ops_impls, FloatAttr};
+
+fn fmt_impls(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let display = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Display").unwrap(),
+    );
+    let lower_exp = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::LowerExp").unwrap(),
+    );
+    let upper_exp = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::UpperExp").unwrap(),
+    );
+
+    quote! {
+        #display
+        #lower_exp
+        #upper_exp
+    }
+}
+
+fn from_str_generics(generics: &Generics, inner: &Type) -> Generics {
+    with_predicates(
+        generics,
+        [parse_quote!(#inner: ::core::str::FromStr<Err = ::core::num::ParseFloatError>)],
+    )
+}
+
+/// `PartialEq`, `Eq`, `PartialOrd` and `Ord` using `total_cmp`, and `Hash` using `to_bits`
+///
+/// `total_cmp` considers two values equal exactly when their bits are equal, so the `Hash` impl is
+/// consistent with `Eq`. This means `0.0 != -0.0`, and NaNs are equal to themselves
+fn total_ord_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    float: &FloatAttr,
+) -> TokenStream {
+    if !float.total_ord {
+        return quote! {};
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let hash_impl = match float.hash {
+        true => quote! {
+            impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+                fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                    ::core::hash::Hash::hash(&<#inner>::to_bits(self.0), state)
+                }
+            }
+        },
+        false => quote! {},
+    };
+
+    quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                <#inner>::total_cmp(&self.0, &other.0).is_eq()
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                <#inner>::total_cmp(&self.0, &other.0)
+            }
+        }
+
+        #hash_impl
+    }
+}
+
+pub fn generate_float_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    float: &FloatAttr,
+    replaced_ops: &[&str],
+) -> TokenStream {
+    let fmt_impls = fmt_impls(name, generics, inner);
+    let ops_impls = float_ops_impls(name, generics, inner, float, replaced_ops);
+    let total_ord_impls = total_ord_impls(name, generics, inner, float);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_str_generics = from_str_generics(generics, inner);
+    let (impl_generics, _, where_clause) = from_str_generics.split_for_impl();
+
+    quote! {
+        #fmt_impls
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::core::num::ParseFloatError;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                <#inner as ::core::str::FromStr>::from_str(s)
+                    .map(<Self as ::microtype::Microtype>::new)
+            }
+        }
+
+        #ops_impls
+        #total_ord_impls
+    }
+}
+
+/// Like `#[int]`, only `FromStr` is implemented, since the other impls could leak the secret
+pub fn secret_float_impls(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_str_generics = from_str_generics(generics, inner);
+    let (impl_generics, _, where_clause) = from_str_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::core::num::ParseFloatError;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                <#inner as ::core::str::FromStr>::from_str(s)
+                    .map(<Self as ::microtype::SecretMicrotype>::new)
+            }
+        }
+    }
+}
+
+/// Arithmetic impls are only generated for finite floats, since the result of an operator is
+/// checked in the same way as a bounded int. Other validators could be violated by the result
+pub fn validated_float_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    error: &Type,
+    float: &FloatAttr,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_str_generics = from_str_generics(generics, inner);
+    let (impl_generics, _, where_clause) = from_str_generics.split_for_impl();
+    let fmt_impls = fmt_impls(name, generics, inner);
+    let ops_impls = float
+        .finite
+        .then(|| float_ops_impls(name, generics, inner, float, &[]));
+    let total_ord_impls = total_ord_impls(name, generics, inner, float);
+
+    quote! {
+        #fmt_impls
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::microtype::ParseError<::core::num::ParseFloatError, #error>;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                let inner = <#inner as ::core::str::FromStr>::from_str(s)
+                    .map_err(::microtype::ParseError::Parse)?;
+                <Self as ::microtype::ValidatedMicrotype>::try_new(inner)
+                    .map_err(::microtype::ParseError::Invalid)
+            }
+        }
+
+        #ops_impls
+        #total_ord_impls
+    }
+}
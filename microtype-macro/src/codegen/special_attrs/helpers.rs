@@ -1,13 +1,133 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, Path, Type};
+use syn::{parse_quote, Attribute, Generics, Ident, Meta, NestedMeta, Path, Type};
+
+use crate::codegen::generics::{with_param, with_predicates};
+
+const SIGNED_PRIMITIVES: [&str; 6] = ["i8", "i16", "i32", "i64", "i128", "isize"];
+const UNSIGNED_PRIMITIVES: [&str; 6] = ["u8", "u16", "u32", "u64", "u128", "usize"];
+const FLOAT_PRIMITIVES: [&str; 2] = ["f32", "f64"];
+
+/// Whether `ty` is spelled as one of `idents`, e.g. `["i64", "u64"]`
+pub fn is_one_of(ty: &Type, idents: &[&str]) -> bool {
+    match ty {
+        Type::Path(path) => idents.iter().any(|ident| path.path.is_ident(ident)),
+        _ => false,
+    }
+}
+
+/// Whether `ty` is spelled as a signed integer primitive
+///
+/// Aliases (e.g. `type Cents = i64;`) can't be detected, since we don't have type information
+pub fn is_signed_primitive(ty: &Type) -> bool {
+    is_one_of(ty, &SIGNED_PRIMITIVES)
+}
+
+/// Whether `ty` is spelled as an integer primitive, with the same caveat as [`is_signed_primitive`]
+pub fn is_int_primitive(ty: &Type) -> bool {
+    is_signed_primitive(ty) || is_one_of(ty, &UNSIGNED_PRIMITIVES)
+}
+
+/// Whether `ty` is spelled as `f32` or `f64`, with the same caveat as [`is_signed_primitive`]
+pub fn is_float_primitive(ty: &Type) -> bool {
+    is_one_of(ty, &FLOAT_PRIMITIVES)
+}
+
+/// The first trait in a `#[derive(...)]` whose last path segment is one of `traits`, which is used
+/// to report derives that would conflict with generated impls
+pub fn find_derive(attrs: &[Attribute], traits: &[&str]) -> Option<Path> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path))
+                if path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| traits.iter().any(|t| segment.ident == t)) =>
+            {
+                Some(path)
+            }
+            _ => None,
+        })
+}
+
+pub fn fmt_impl(name: &Ident, generics: &Generics, inner: &Type, trait_name: &Path) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let fmt_generics = with_predicates(generics, [parse_quote!(#inner: #trait_name)]);
+    let (impl_generics, _, where_clause) = fmt_generics.split_for_impl();
 
-pub fn fmt_impl(name: &Ident, inner: &Type, trait_name: &Path) -> TokenStream {
     quote! {
-        impl #trait_name for #name {
+        impl #impl_generics #trait_name for #name #ty_generics #where_clause {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 <#inner as #trait_name>::fmt(&self.0, f)
             }
         }
     }
 }
+
+/// `Serialize` and `Deserialize` impls that use `Display` and `FromStr`, for microtypes whose
+/// string form isn't just the inner value
+pub fn string_serde_impls(name: &Ident, generics: &Generics) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let de_generics = with_param(generics, parse_quote!('__de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> Result<__S::Ok, __S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl #de_impl_generics ::serde::Deserialize<'__de> for #name #ty_generics #where_clause {
+            fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> Result<Self, __D::Error> {
+                let s = <::microtype::__private::alloc::string::String as ::serde::Deserialize<'__de>>::deserialize(deserializer)?;
+                <Self as ::core::str::FromStr>::from_str(&s).map_err(::serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_int_primitives() {
+        assert!(is_signed_primitive(&parse_quote!(i64)));
+        assert!(!is_signed_primitive(&parse_quote!(u64)));
+        assert!(!is_signed_primitive(&parse_quote!(T)));
+
+        assert!(is_int_primitive(&parse_quote!(u8)));
+        assert!(is_int_primitive(&parse_quote!(isize)));
+        assert!(!is_int_primitive(&parse_quote!(f64)));
+        assert!(!is_int_primitive(&parse_quote!(std::num::NonZeroU32)));
+    }
+
+    #[test]
+    fn finds_derives() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[doc = "docs"]),
+            parse_quote!(#[derive(Debug, Clone)]),
+            parse_quote!(#[derive(::core::cmp::PartialEq)]),
+        ];
+
+        let path = find_derive(&attrs, &["Eq", "PartialEq"]).unwrap();
+        assert_eq!(path, parse_quote!(::core::cmp::PartialEq));
+        assert!(find_derive(&attrs, &["Hash"]).is_none());
+    }
+
+    #[test]
+    fn detects_float_primitives() {
+        assert!(is_float_primitive(&parse_quote!(f32)));
+        assert!(is_float_primitive(&parse_quote!(f64)));
+        assert!(!is_float_primitive(&parse_quote!(i64)));
+        assert!(!is_float_primitive(&parse_quote!(NotNan<f64>)));
+    }
+}
@@ -0,0 +1,482 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_str,
+    spanned::Spanned,
+    Attribute, Generics, Ident, Token, Type,
+};
+
+use super::{
+    helpers::{fmt_impl, is_one_of, string_serde_impls},
+    int::int_fmt_and_parse_impls,
+};
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[id(kind)]`, where `kind` is one of `uuid_v4`, `uuid_v7`, `ulid`, `sequence` or `snowflake(node = ..., epoch = ...)`"))
+}
+
+fn duplicate_id(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `id` attribute found"))
+}
+
+/// Twitter's epoch, 2010-11-04T01:42:54.657Z, in milliseconds since the Unix epoch
+const DEFAULT_SNOWFLAKE_EPOCH: u64 = 1_288_834_974_657;
+
+/// How `generate()` or `next()` creates a new ID
+#[derive(Debug)]
+pub enum IdKind {
+    UuidV4,
+    UuidV7,
+    Ulid,
+    /// An atomic counter, starting at 1
+    Sequence,
+    Snowflake(SnowflakeOptions),
+}
+
+/// The options in `#[id(snowflake(...))]`
+///
+/// The values are kept as tokens, so they can be any const expression
+#[derive(Debug)]
+pub struct SnowflakeOptions {
+    /// The `u16` node ID, which must be less than 1024
+    pub node: TokenStream,
+    /// The `u64` epoch, in milliseconds since the Unix epoch
+    pub epoch: Option<TokenStream>,
+}
+
+impl IdKind {
+    /// Whether this is `sequence` or `snowflake(...)`, which are generated by `next()` and need an
+    /// `#[int]` inner type, rather than `generate()` and a UUID or ULID
+    pub fn is_int(&self) -> bool {
+        matches!(self, Self::Sequence | Self::Snowflake(_))
+    }
+
+    /// The traits generated for this kind of ID, which therefore can't be derived
+    ///
+    /// Integer IDs are already ordered by creation time, so they can derive any of them
+    pub fn generated_traits(&self) -> &'static [&'static str] {
+        match self {
+            Self::UuidV7 | Self::Ulid => &["PartialEq", "Eq", "PartialOrd", "Ord", "Hash"],
+            Self::UuidV4 => &["PartialEq", "Eq", "Hash"],
+            Self::Sequence | Self::Snowflake(_) => &[],
+        }
+    }
+
+    /// Whether `inner` can hold this kind of ID, which is only checked for integer IDs
+    ///
+    /// Sequences use the matching atomic, and there are no stable 128-bit atomics. Snowflakes are
+    /// 63 bits
+    pub fn supports_inner(&self, inner: &Type) -> bool {
+        match self {
+            Self::UuidV4 | Self::UuidV7 | Self::Ulid => true,
+            Self::Sequence => atomic_for(inner).is_some(),
+            Self::Snowflake(_) => is_one_of(inner, &["i64", "u64"]),
+        }
+    }
+}
+
+impl Parse for IdKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind: Ident = input.parse()?;
+
+        match kind.to_string().as_str() {
+            "uuid_v4" => Ok(Self::UuidV4),
+            "uuid_v7" => Ok(Self::UuidV7),
+            "ulid" => Ok(Self::Ulid),
+            "sequence" => Ok(Self::Sequence),
+            "snowflake" => {
+                let content;
+                parenthesized!(content in input);
+                Ok(Self::Snowflake(content.parse()?))
+            }
+            _ => Err(syn::Error::new(kind.span(), "unknown ID kind")),
+        }
+    }
+}
+
+impl Parse for SnowflakeOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut node = None;
+        let mut epoch = None;
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            let _: Token![=] = input.parse()?;
+
+            let mut value = TokenStream::new();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                value.extend([input.parse::<TokenTree>()?]);
+            }
+            if value.is_empty() {
+                return Err(input.error("expected a value"));
+            }
+
+            let slot = match key.to_string().as_str() {
+                "node" => &mut node,
+                "epoch" => &mut epoch,
+                _ => return Err(syn::Error::new(key.span(), "expected `node` or `epoch`")),
+            };
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(key.span(), "duplicate key"));
+            }
+
+            let _: Option<Token![,]> = input.parse()?;
+        }
+
+        match node {
+            Some(node) => Ok(Self { node, epoch }),
+            None => Err(input.error("expected `node = ...`")),
+        }
+    }
+}
+
+/// The contents of an `#[id(kind)]` attribute
+#[derive(Debug)]
+pub struct IdAttr {
+    pub kind: IdKind,
+    /// The span of `id`
+    pub span: Span,
+}
+
+impl IdAttr {
+    /// The attribute as written, for error messages
+    pub fn display(&self) -> &'static str {
+        match self.kind {
+            IdKind::UuidV4 => "#[id(uuid_v4)]",
+            IdKind::UuidV7 => "#[id(uuid_v7)]",
+            IdKind::Ulid => "#[id(ulid)]",
+            IdKind::Sequence => "#[id(sequence)]",
+            IdKind::Snowflake(_) => "#[id(snowflake(...))]",
+        }
+    }
+}
+
+/// The atomic with the same size and signedness as `inner`, if there is one
+fn atomic_for(inner: &Type) -> Option<Type> {
+    let atomic = match inner {
+        Type::Path(path) => match path.path.get_ident()?.to_string().as_str() {
+            "i8" => "AtomicI8",
+            "i16" => "AtomicI16",
+            "i32" => "AtomicI32",
+            "i64" => "AtomicI64",
+            "isize" => "AtomicIsize",
+            "u8" => "AtomicU8",
+            "u16" => "AtomicU16",
+            "u32" => "AtomicU32",
+            "u64" => "AtomicU64",
+            "usize" => "AtomicUsize",
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    parse_str(&format!("::core::sync::atomic::{atomic}")).ok()
+}
+
+fn parse_id_attr(attr: &Attribute) -> Result<IdAttr, TokenStream> {
+    match attr.parse_args() {
+        Ok(kind) => Ok(IdAttr {
+            kind,
+            span: attr.path.span(),
+        }),
+        Err(e) => Err(generic_err(e.span())),
+    }
+}
+
+pub fn strip_id_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<IdAttr>), TokenStream> {
+    let (id, attrs): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|attr| attr.path.is_ident("id"));
+
+    let id = match &id[..] {
+        [] => None,
+        [single] => Some(parse_id_attr(single)?),
+        [_, second, ..] => return Err(duplicate_id(second.span())),
+    };
+
+    Ok((attrs, id))
+}
+
+/// `generate()`, `Display`, `FromStr`, the comparison traits, and the serde impls if `serde` is
+/// true
+///
+/// The comparison traits just compare the inner values, but are generated so that `Ord` is only
+/// available when it follows creation time. IDs are always serialized as strings, even in binary
+/// formats
+fn uuid_or_ulid_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    id: &IdAttr,
+    serde: bool,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let display = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Display").unwrap(),
+    );
+
+    let (generate, doc) = match &id.kind {
+        IdKind::UuidV4 => (
+            quote!(::microtype::uuid::Uuid::new_v4()),
+            "Generate a new, random (version 4) UUID",
+        ),
+        IdKind::UuidV7 => (
+            quote!(::microtype::uuid::Uuid::now_v7()),
+            "Generate a new (version 7) UUID, which is ordered by creation time",
+        ),
+        IdKind::Ulid => (
+            quote!(::microtype::ulid::Ulid::new()),
+            "Generate a new ULID, which is ordered by creation time",
+        ),
+        IdKind::Sequence | IdKind::Snowflake(_) => unreachable!("integer IDs use `next()`"),
+    };
+
+    let ord_impls = matches!(id.kind, IdKind::UuidV7 | IdKind::Ulid).then(|| {
+        quote! {
+            impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
+                fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                    ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+                }
+            }
+
+            impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    ::core::cmp::Ord::cmp(&self.0, &other.0)
+                }
+            }
+        }
+    });
+
+    let serde_impls = serde.then(|| string_serde_impls(name, generics));
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #doc]
+            pub fn generate() -> Self {
+                <Self as ::microtype::Microtype>::new(#generate)
+            }
+        }
+
+        #display
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = <#inner as ::core::str::FromStr>::Err;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                <#inner as ::core::str::FromStr>::from_str(s)
+                    .map(<Self as ::microtype::Microtype>::new)
+            }
+        }
+
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                ::core::hash::Hash::hash(&self.0, state)
+            }
+        }
+
+        #ord_impls
+        #serde_impls
+    }
+}
+
+/// `next()` and the `#[int]` formatting and `FromStr` impls, plus accessors for the parts of a
+/// snowflake
+///
+/// The state lives in a `static` inside `next()`, so it is shared by every instantiation of a
+/// generic microtype
+fn int_id_impls(name: &Ident, generics: &Generics, inner: &Type, id: &IdAttr) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fmt_and_parse_impls = int_fmt_and_parse_impls(name, generics, inner);
+    let exhausted = format!("the `{name}` sequence is exhausted");
+
+    let methods = match &id.kind {
+        IdKind::Sequence => {
+            let atomic = atomic_for(inner).expect("checked by `IdKind::supports_inner`");
+            quote! {
+                /// The next ID in the sequence, starting at 1
+                ///
+                /// # Panics
+                ///
+                /// Panics if the sequence reaches the inner type's `MAX`
+                pub fn next() -> Self {
+                    static NEXT: #atomic = #atomic::new(1);
+
+                    let ordering = ::core::sync::atomic::Ordering::Relaxed;
+                    let id = NEXT
+                        .fetch_update(ordering, ordering, |id| id.checked_add(1))
+                        .expect(#exhausted);
+                    <Self as ::microtype::Microtype>::new(id)
+                }
+            }
+        }
+        IdKind::Snowflake(SnowflakeOptions { node, epoch }) => {
+            let epoch = match epoch {
+                Some(epoch) => quote!(#epoch),
+                None => quote!(#DEFAULT_SNOWFLAKE_EPOCH),
+            };
+            let snowflake = quote!(::microtype::__private::snowflake);
+            quote! {
+                /// The epoch that timestamps are relative to, in milliseconds since the Unix epoch
+                pub const EPOCH_MS: ::core::primitive::u64 = #epoch;
+
+                /// Generate a new snowflake, which is ordered by creation time
+                ///
+                /// The layout is 41 bits of milliseconds since `EPOCH_MS`, 10 bits of node ID and
+                /// 12 bits of sequence number. If more than 4096 IDs are generated in the same
+                /// millisecond, this waits for the next one
+                pub fn next() -> Self {
+                    static GENERATOR: #snowflake::Generator = #snowflake::Generator::new(#node, #epoch);
+                    <Self as ::microtype::Microtype>::new(GENERATOR.next() as #inner)
+                }
+
+                /// When this ID was generated, in milliseconds since the Unix epoch
+                pub fn timestamp_ms(&self) -> ::core::primitive::u64 {
+                    #snowflake::timestamp_ms(self.0 as ::core::primitive::u64, Self::EPOCH_MS)
+                }
+
+                /// When this ID was generated
+                pub fn timestamp(&self) -> #snowflake::SystemTime {
+                    #snowflake::timestamp(self.0 as ::core::primitive::u64, Self::EPOCH_MS)
+                }
+
+                /// The node that generated this ID
+                pub fn node(&self) -> ::core::primitive::u16 {
+                    #snowflake::node(self.0 as ::core::primitive::u64)
+                }
+
+                /// The sequence number of this ID within its millisecond
+                pub fn sequence(&self) -> ::core::primitive::u16 {
+                    #snowflake::sequence(self.0 as ::core::primitive::u64)
+                }
+            }
+        }
+        IdKind::UuidV4 | IdKind::UuidV7 | IdKind::Ulid => {
+            unreachable!("UUIDs and ULIDs use `generate()`")
+        }
+    };
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #methods
+        }
+
+        #fmt_and_parse_impls
+    }
+}
+
+/// The impls for an `#[id(...)]` microtype
+///
+/// Integer IDs are serialized like any other `#[int]` microtype, so `serde` only applies to UUIDs
+/// and ULIDs
+pub fn id_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    id: &IdAttr,
+    serde: bool,
+) -> TokenStream {
+    match id.kind.is_int() {
+        true => int_id_impls(name, generics, inner, id),
+        false => uuid_or_ulid_impls(name, generics, inner, id, serde),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    fn strip(s: &str) -> Result<Option<IdAttr>, TokenStream> {
+        let microtype: MicrotypeMacro = parse_str(s).unwrap();
+        strip_id_attr(microtype.0[0].attrs.clone()).map(|(_, id)| id)
+    }
+
+    fn kind(s: &str) -> IdKind {
+        strip(&format!("#[derive(Debug)] #[id({s})] Uuid {{ UserId }}"))
+            .unwrap()
+            .unwrap()
+            .kind
+    }
+
+    #[test]
+    fn strips_id_attr() {
+        assert!(matches!(kind("uuid_v4"), IdKind::UuidV4));
+        assert!(matches!(kind("uuid_v7"), IdKind::UuidV7));
+        assert!(matches!(kind("ulid"), IdKind::Ulid));
+        assert!(matches!(kind("sequence"), IdKind::Sequence));
+
+        assert!(strip("Uuid { UserId }").unwrap().is_none());
+
+        for s in [
+            "#[id] Uuid { UserId }",
+            "#[id()] Uuid { UserId }",
+            "#[id(uuid_v1)] Uuid { UserId }",
+            "#[id(uuid_v4, ulid)] Uuid { UserId }",
+            r#"#[id = "ulid"] Uuid { UserId }"#,
+            "#[id(ulid)] #[id(ulid)] Uuid { UserId }",
+        ] {
+            strip(s).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn parses_snowflake_options() {
+        let options = match kind("snowflake(node = NODE + 1, epoch = 1_600_000_000_000)") {
+            IdKind::Snowflake(options) => options,
+            other => panic!("expected snowflake, got {other:?}"),
+        };
+        assert_eq!(options.node.to_string(), "NODE + 1");
+        assert_eq!(options.epoch.unwrap().to_string(), "1_600_000_000_000");
+
+        let options = match kind("snowflake(node = 3)") {
+            IdKind::Snowflake(options) => options,
+            other => panic!("expected snowflake, got {other:?}"),
+        };
+        assert_eq!(options.node.to_string(), "3");
+        assert!(options.epoch.is_none());
+
+        for s in [
+            "snowflake",
+            "snowflake()",
+            "snowflake(epoch = 0)",
+            "snowflake(node)",
+            "snowflake(node = )",
+            "snowflake(node = 1, node = 2)",
+            "snowflake(node = 1, shard = 2)",
+        ] {
+            strip(&format!("#[id({s})] i64 {{ EventId }}")).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn checks_inner_type() {
+        let inner = |s: &str| parse_str::<Type>(s).unwrap();
+
+        assert!(IdKind::Sequence.supports_inner(&inner("u8")));
+        assert!(IdKind::Sequence.supports_inner(&inner("i64")));
+        assert!(!IdKind::Sequence.supports_inner(&inner("u128")));
+        assert!(!IdKind::Sequence.supports_inner(&inner("String")));
+
+        let snowflake = kind("snowflake(node = 0)");
+        assert!(snowflake.supports_inner(&inner("i64")));
+        assert!(snowflake.supports_inner(&inner("u64")));
+        assert!(!snowflake.supports_inner(&inner("u32")));
+    }
+}
@@ -1,17 +1,58 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse_str, Ident, Type};
+use syn::{parse_quote, parse_str, Generics, Ident, Type};
 
-use super::helpers::fmt_impl;
+use crate::codegen::generics::with_predicates;
 
-pub fn generate_int_impls(name: &Ident, inner: &Type) -> TokenStream {
-    let display = fmt_impl(name, inner, &parse_str("::core::fmt::Display").unwrap());
-    let octal = fmt_impl(name, inner, &parse_str("::core::fmt::Octal").unwrap());
-    let lower_hex = fmt_impl(name, inner, &parse_str("::core::fmt::LowerHex").unwrap());
-    let upper_hex = fmt_impl(name, inner, &parse_str("::core::fmt::UpperHex").unwrap());
-    let binary = fmt_impl(name, inner, &parse_str("::core::fmt::Binary").unwrap());
-    let lower_exp = fmt_impl(name, inner, &parse_str("::core::fmt::LowerExp").unwrap());
-    let upper_exp = fmt_impl(name, inner, &parse_str("::core::fmt::UpperExp").unwrap());
+use super::{
+    helpers::{fmt_impl, is_int_primitive, is_signed_primitive, string_serde_impls},
+    ops::ops_impls,
+    IntAttr, PublicEncoding,
+};
+
+fn fmt_impls(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let display = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Display").unwrap(),
+    );
+    let octal = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Octal").unwrap(),
+    );
+    let lower_hex = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::LowerHex").unwrap(),
+    );
+    let upper_hex = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::UpperHex").unwrap(),
+    );
+    let binary = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Binary").unwrap(),
+    );
+    let lower_exp = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::LowerExp").unwrap(),
+    );
+    let upper_exp = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::UpperExp").unwrap(),
+    );
 
     quote! {
         #display
@@ -21,79 +62,336 @@ pub fn generate_int_impls(name: &Ident, inner: &Type) -> TokenStream {
         #binary
         #lower_exp
         #upper_exp
+    }
+}
 
-        impl ::core::str::FromStr for #name {
-            type Err = ::core::num::ParseIntError;
+fn from_str_generics(generics: &Generics, inner: &Type) -> Generics {
+    with_predicates(
+        generics,
+        [parse_quote!(#inner: ::core::str::FromStr<Err = ::core::num::ParseIntError>)],
+    )
+}
 
-            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
-                <#inner as ::core::str::FromStr>::from_str(s).map(Self)
-            }
+/// Inherent `checked_*`, `saturating_*`, `wrapping_*` and `overflowing_*` methods, as well as
+/// `pow` and `abs`, which return the microtype rather than the inner type
+///
+/// These only exist on the primitives, so can't be generated for other inner types. Bounded
+/// microtypes only get the `checked_*` methods, which also return `None` if the result is out of
+/// range
+fn arithmetic_methods(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    bounded: bool,
+) -> TokenStream {
+    if !is_int_primitive(inner) {
+        return quote! {};
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let new = quote!(<Self as ::microtype::Microtype>::new);
+    let checked_new = match bounded {
+        true => {
+            quote!(.and_then(|value| <Self as ::microtype::ValidatedMicrotype>::try_new(value).ok()))
         }
+        false => quote!(.map(#new)),
+    };
+    let signed = is_signed_primitive(inner);
+    let out_of_range = match bounded {
+        true => ", or if the result is out of range",
+        false => "",
+    };
+    let mut methods = TokenStream::new();
 
-        impl ::core::ops::Add for #name {
-            type Output = #name;
-            fn add(self, rhs: Self) -> Self::Output {
-                Self(self.0 + rhs.0)
+    let ident = |prefix: &str, method: &str| Ident::new(&format!("{prefix}{method}"), name.span());
+    let ops = [
+        ("add", "addition"),
+        ("sub", "subtraction"),
+        ("mul", "multiplication"),
+        ("div", "division"),
+        ("rem", "remainder"),
+    ];
+
+    for (method, op) in ops {
+        let checked = ident("checked_", method);
+        let by_zero = match method {
+            "div" | "rem" => " or if `rhs` is zero",
+            _ => "",
+        };
+        let doc = format!("Checked {op}, returning `None` on overflow{by_zero}{out_of_range}");
+        methods.extend(quote! {
+            #[doc = #doc]
+            pub fn #checked(self, rhs: Self) -> ::core::option::Option<Self> {
+                <#inner>::#checked(self.0, rhs.0) #checked_new
             }
+        });
+    }
+
+    let pow_doc = format!("Checked exponentiation, returning `None` on overflow{out_of_range}");
+    let neg_doc = format!("Checked negation, returning `None` on overflow{out_of_range}");
+    methods.extend(quote! {
+        #[doc = #pow_doc]
+        pub fn checked_pow(self, exp: ::core::primitive::u32) -> ::core::option::Option<Self> {
+            <#inner>::checked_pow(self.0, exp) #checked_new
         }
 
-        impl ::core::ops::Sub for #name {
-            type Output = #name;
-            fn sub(self, rhs: Self) -> Self::Output {
-                Self(self.0 - rhs.0)
-            }
+        #[doc = #neg_doc]
+        pub fn checked_neg(self) -> ::core::option::Option<Self> {
+            <#inner>::checked_neg(self.0) #checked_new
         }
+    });
 
-        impl ::core::ops::Mul for #name {
-            type Output = #name;
-            fn mul(self, rhs: Self) -> Self::Output {
-                Self(self.0 * rhs.0)
+    if signed {
+        let doc = format!("Checked absolute value, returning `None` on overflow{out_of_range}");
+        methods.extend(quote! {
+            #[doc = #doc]
+            pub fn checked_abs(self) -> ::core::option::Option<Self> {
+                <#inner>::checked_abs(self.0) #checked_new
             }
+        });
+    }
+
+    if !bounded {
+        for (method, op) in ops {
+            let wrapping = ident("wrapping_", method);
+            let overflowing = ident("overflowing_", method);
+            let wrapping_doc = format!("Wrapping {op}, which wraps around on overflow");
+            let overflowing_doc =
+                format!("Overflowing {op}, returning the wrapped result and whether it overflowed");
+            methods.extend(quote! {
+                #[doc = #wrapping_doc]
+                pub fn #wrapping(self, rhs: Self) -> Self {
+                    #new(<#inner>::#wrapping(self.0, rhs.0))
+                }
+
+                #[doc = #overflowing_doc]
+                pub fn #overflowing(self, rhs: Self) -> (Self, bool) {
+                    let (value, overflowed) = <#inner>::#overflowing(self.0, rhs.0);
+                    (#new(value), overflowed)
+                }
+            });
         }
 
-        impl ::core::ops::Div for #name {
-            type Output = #name;
-            fn div(self, rhs: Self) -> Self::Output {
-                Self(self.0 / rhs.0)
-            }
+        for (method, op) in &ops[..4] {
+            let saturating = ident("saturating_", method);
+            let doc = format!("Saturating {op}, which stops at the bounds instead of overflowing");
+            methods.extend(quote! {
+                #[doc = #doc]
+                pub fn #saturating(self, rhs: Self) -> Self {
+                    #new(<#inner>::#saturating(self.0, rhs.0))
+                }
+            });
         }
 
-        impl ::core::ops::Rem for #name {
-            type Output = #name;
-            fn rem(self, rhs: Self) -> Self::Output {
-                Self(self.0 % rhs.0)
+        methods.extend(quote! {
+            /// Raises the value to the power of `exp`, overflowing like the inner type
+            pub fn pow(self, exp: ::core::primitive::u32) -> Self {
+                #new(<#inner>::pow(self.0, exp))
+            }
+
+            /// Saturating exponentiation, which stops at the bounds instead of overflowing
+            pub fn saturating_pow(self, exp: ::core::primitive::u32) -> Self {
+                #new(<#inner>::saturating_pow(self.0, exp))
+            }
+
+            /// Wrapping exponentiation, which wraps around on overflow
+            pub fn wrapping_pow(self, exp: ::core::primitive::u32) -> Self {
+                #new(<#inner>::wrapping_pow(self.0, exp))
+            }
+
+            /// Overflowing exponentiation, returning the wrapped result and whether it overflowed
+            pub fn overflowing_pow(self, exp: ::core::primitive::u32) -> (Self, bool) {
+                let (value, overflowed) = <#inner>::overflowing_pow(self.0, exp);
+                (#new(value), overflowed)
+            }
+
+            /// Wrapping negation, which wraps around on overflow
+            pub fn wrapping_neg(self) -> Self {
+                #new(<#inner>::wrapping_neg(self.0))
+            }
+        });
+    }
+
+    if !bounded && signed {
+        methods.extend(quote! {
+            /// The absolute value, overflowing like the inner type
+            pub fn abs(self) -> Self {
+                #new(<#inner>::abs(self.0))
+            }
+
+            /// Saturating absolute value, which stops at the bounds instead of overflowing
+            pub fn saturating_abs(self) -> Self {
+                #new(<#inner>::saturating_abs(self.0))
+            }
+
+            /// Wrapping absolute value, which wraps around on overflow
+            pub fn wrapping_abs(self) -> Self {
+                #new(<#inner>::wrapping_abs(self.0))
             }
+
+            /// Saturating negation, which stops at the bounds instead of overflowing
+            pub fn saturating_neg(self) -> Self {
+                #new(<#inner>::saturating_neg(self.0))
+            }
+        });
+    }
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #methods
         }
+    }
+}
 
-        impl ::core::ops::AddAssign for #name {
-            fn add_assign(&mut self, rhs: Self) {
-                self.0 += rhs.0
+/// The formatting traits and `FromStr`, without any arithmetic, which is all that `#[int]` IDs get
+pub fn int_fmt_and_parse_impls(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let fmt_impls = fmt_impls(name, generics, inner);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_str_generics = from_str_generics(generics, inner);
+    let (impl_generics, _, where_clause) = from_str_generics.split_for_impl();
+
+    quote! {
+        #fmt_impls
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::core::num::ParseIntError;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                <#inner as ::core::str::FromStr>::from_str(s)
+                    .map(<Self as ::microtype::Microtype>::new)
             }
         }
+    }
+}
+
+/// `Display`, `FromStr` and the serde impls (if `serde` is true), which all use the public encoding
+///
+/// The other formatting traits aren't implemented, since they would show the number. The alphabet
+/// is shuffled at compile time
+fn public_encoding_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    encoding: &PublicEncoding,
+    serde: bool,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let microtype = name.to_string();
+    let PublicEncoding {
+        alphabet,
+        min_length,
+        ..
+    } = encoding;
+    let len = alphabet.value().len();
+    let sqids = quote!(::microtype::__private::sqids);
+    let alphabet = quote! {
+        const ALPHABET: [::core::primitive::u8; #len] = #sqids::shuffle(*#alphabet);
+    };
+    let min_length = *min_length as usize;
+    let serde_impls = serde.then(|| string_serde_impls(name, generics));
 
-        impl ::core::ops::SubAssign for #name {
-            fn sub_assign(&mut self, rhs: Self) {
-                self.0 -= rhs.0
+    quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #alphabet
+                f.pad(&#sqids::encode(self.0 as ::core::primitive::u64, &ALPHABET, #min_length))
             }
         }
 
-        impl ::core::ops::MulAssign for #name {
-            fn mul_assign(&mut self, rhs: Self) {
-                self.0 *= rhs.0
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::microtype::PublicEncodingError;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                #alphabet
+                #sqids::decode(s, &ALPHABET, #min_length)
+                    .and_then(|number| <#inner as ::core::convert::TryFrom<::core::primitive::u64>>::try_from(number).ok())
+                    .map(<Self as ::microtype::Microtype>::new)
+                    .ok_or(::microtype::PublicEncodingError { microtype: #microtype })
             }
         }
 
-        impl ::core::ops::DivAssign for #name {
-            fn div_assign(&mut self, rhs: Self) {
-                self.0 /= rhs.0
+        #serde_impls
+    }
+}
+
+pub fn generate_int_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    int: &IntAttr,
+    replaced_ops: &[&str],
+    serde: bool,
+) -> TokenStream {
+    let fmt_and_parse_impls = match &int.public_encoding {
+        Some(encoding) => public_encoding_impls(name, generics, inner, encoding, serde),
+        None => int_fmt_and_parse_impls(name, generics, inner),
+    };
+    let ops_impls = ops_impls(name, generics, inner, int, replaced_ops);
+    let arithmetic_methods = arithmetic_methods(name, generics, inner, false);
+
+    quote! {
+        #fmt_and_parse_impls
+        #ops_impls
+        #arithmetic_methods
+    }
+}
+
+/// Formatting traits (e.g. `Display`, `LowerHex`) and arithmetic are deliberately not implemented,
+/// since they would make it too easy to leak the secret
+pub fn secret_int_impls(name: &Ident, generics: &Generics, inner: &Type) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_str_generics = from_str_generics(generics, inner);
+    let (impl_generics, _, where_clause) = from_str_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::core::num::ParseIntError;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                <#inner as ::core::str::FromStr>::from_str(s)
+                    .map(<Self as ::microtype::SecretMicrotype>::new)
             }
         }
+    }
+}
+
+/// Arithmetic impls are not generated for validated ints, since the result could violate the
+/// validator. Bounded ints are the exception, since their operators return `None` when the result
+/// is out of range
+pub fn validated_int_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    error: &Type,
+    int: &IntAttr,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let from_str_generics = from_str_generics(generics, inner);
+    let (impl_generics, _, where_clause) = from_str_generics.split_for_impl();
+    let fmt_impls = fmt_impls(name, generics, inner);
+    let arithmetic = int.range.as_ref().map(|_| {
+        let ops_impls = ops_impls(name, generics, inner, int, &[]);
+        let arithmetic_methods = arithmetic_methods(name, generics, inner, true);
+        quote! {
+            #ops_impls
+            #arithmetic_methods
+        }
+    });
 
-        impl ::core::ops::RemAssign for #name {
-            fn rem_assign(&mut self, rhs: Self) {
-                self.0 %= rhs.0
+    quote! {
+        #fmt_impls
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::microtype::ParseError<::core::num::ParseIntError, #error>;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                let inner = <#inner as ::core::str::FromStr>::from_str(s)
+                    .map_err(::microtype::ParseError::Parse)?;
+                <Self as ::microtype::ValidatedMicrotype>::try_new(inner)
+                    .map_err(::microtype::ParseError::Invalid)
             }
         }
 
+        #arithmetic
     }
 }
@@ -1,22 +1,53 @@
+mod checksum;
+mod cross_ops;
 mod diesel;
+mod float;
 mod helpers;
+mod id;
 mod int;
+mod normalize;
+mod one_of;
+mod ops;
+mod overrides;
+mod prefixed_id;
+mod private;
 mod string;
+mod tagged;
 mod type_annotation;
-
-pub use int::generate_int_impls;
+mod validate;
+
+pub use checksum::{checksum_check, checksum_impl, ChecksumAttr};
+pub use cross_ops::{cross_ops_impls, find_unowned_cross_op, CrossOp};
+pub use float::{generate_float_impls, secret_float_impls, validated_float_impls};
+pub use helpers::{find_derive, is_float_primitive, is_int_primitive};
+pub use id::{id_impls, IdAttr, IdKind};
+pub use int::{generate_int_impls, secret_int_impls, validated_int_impls};
+pub use normalize::{normalize_inner, normalizes};
+pub use one_of::{one_of_check, one_of_impl, OneOfAttr};
+pub use overrides::FeatureOverrides;
+pub use prefixed_id::{prefixed_id_check, prefixed_id_impls, PrefixedIdAttr};
 pub use string::*;
 
-pub use type_annotation::TypeAnnotation;
+pub use type_annotation::{
+    CaseFolding, FloatAttr, IntAttr, IntRange, Normalization, Overflow, PublicEncoding, StringAttr,
+    TypeAnnotation,
+};
+pub use validate::ValidateAttr;
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote_spanned;
 use syn::{spanned::Spanned, Attribute, Ident, Meta, NestedMeta, Path, Type};
 
-use self::{diesel::find_diesel_attr, type_annotation::strip_type_annotation};
+use self::{
+    checksum::strip_checksum_attr, cross_ops::strip_cross_ops_attr, diesel::find_diesel_attr,
+    id::strip_id_attr, normalize::strip_normalize_attr, one_of::strip_one_of_attr,
+    overrides::strip_feature_overrides, prefixed_id::strip_prefixed_id_attr,
+    private::strip_private_attr, tagged::strip_tagged_attr, type_annotation::strip_type_annotation,
+    validate::strip_validate_attr,
+};
 
 fn generic_err(span: Span) -> TokenStream {
-    quote_spanned!(span => compile_error!("expected either `#[secret]` or `#[secret(serialize)]`"))
+    quote_spanned!(span => compile_error!("expected `#[secret]`, `#[secret(serialize)]`, `#[secret(hash)]` or `#[secret(serialize, hash)]`"))
 }
 
 fn duplicate_secret(span: Span) -> TokenStream {
@@ -36,26 +67,36 @@ pub fn strip_special_attrs(
         [single] => {
             let secret_attr = match single.parse_meta() {
                 Ok(Meta::List(list)) => {
-                    let nested: Vec<_> = list.nested.iter().collect();
-                    let serialize = match &nested[..] {
-                        // it's just `#[secret]`
-                        [] => None,
-                        // `#[secret(serialize)]`
-                        [NestedMeta::Meta(Meta::Path(path))] if path.is_ident("serialize") => {
-                            let ident = Ident::new("serialize", path.span());
-                            Some(ident)
+                    let mut serialize = None;
+                    let mut hash = None;
+                    for nested in &list.nested {
+                        let (slot, ident) = match nested {
+                            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("serialize") => {
+                                (&mut serialize, Ident::new("serialize", path.span()))
+                            }
+                            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("hash") => {
+                                (&mut hash, Ident::new("hash", path.span()))
+                            }
+                            other => return Err(generic_err(other.span())),
+                        };
+                        if slot.is_some() {
+                            return Err(generic_err(ident.span()));
                         }
-                        // anything else
-                        [other, ..] => return Err(generic_err(other.span())),
-                    };
+                        *slot = Some(ident);
+                    }
 
                     let path = single.path.clone();
 
-                    SecretAttr { path, serialize }
+                    SecretAttr {
+                        path,
+                        serialize,
+                        hash,
+                    }
                 }
                 Ok(Meta::Path(path)) => SecretAttr {
                     path,
                     serialize: None,
+                    hash: None,
                 },
                 Ok(other) => {
                     println!("other: {other:?}");
@@ -69,12 +110,32 @@ pub fn strip_special_attrs(
     };
 
     let (attrs, type_annotation) = strip_type_annotation(attrs)?;
+    let (attrs, validate) = strip_validate_attr(attrs)?;
+    let (attrs, normalize) = strip_normalize_attr(attrs)?;
+    let (attrs, one_of) = strip_one_of_attr(attrs)?;
+    let (attrs, prefixed_id) = strip_prefixed_id_attr(attrs)?;
+    let (attrs, id) = strip_id_attr(attrs)?;
+    let (attrs, checksum) = strip_checksum_attr(attrs)?;
+    let (attrs, private) = strip_private_attr(attrs)?;
+    let (attrs, tagged) = strip_tagged_attr(attrs)?;
+    let (attrs, overrides) = strip_feature_overrides(attrs)?;
+    let (attrs, cross_ops) = strip_cross_ops_attr(attrs)?;
     let diesel_type = find_diesel_attr(&attrs);
 
     let special_attrs = SpecialAttrs {
         secret,
         type_annotation,
         diesel_type,
+        validate,
+        normalize,
+        one_of,
+        prefixed_id,
+        id,
+        checksum,
+        private,
+        tagged,
+        overrides,
+        cross_ops,
     };
 
     Ok((attrs, special_attrs))
@@ -84,10 +145,22 @@ pub struct SpecialAttrs {
     pub secret: Option<SecretAttr>,
     pub type_annotation: Option<TypeAnnotation>,
     pub diesel_type: Option<Type>,
+    pub validate: Option<ValidateAttr>,
+    pub normalize: Option<Path>,
+    pub one_of: Option<OneOfAttr>,
+    pub prefixed_id: Option<PrefixedIdAttr>,
+    pub id: Option<IdAttr>,
+    pub checksum: Option<ChecksumAttr>,
+    pub private: bool,
+    pub tagged: Option<Path>,
+    pub overrides: FeatureOverrides,
+    pub cross_ops: Vec<CrossOp>,
 }
 
 pub struct SecretAttr {
     pub serialize: Option<Ident>,
+    /// `Hash` with a keyed hash, and `Eq` with a constant-time comparison
+    pub hash: Option<Ident>,
     pub path: Path,
 }
 
@@ -115,7 +188,7 @@ mod tests {
         ) = strip_special_attrs(attrs).unwrap();
         assert!(attrs.len() == 1);
         assert!(secret.is_some());
-        assert!(type_annotation.unwrap() == TypeAnnotation::String);
+        assert!(matches!(type_annotation, Some(TypeAnnotation::String(_))));
     }
 
     #[test]
@@ -129,4 +202,30 @@ mod tests {
         assert!(secret.is_some());
         assert!(secret.unwrap().serialize.is_some());
     }
+
+    #[test]
+    fn removes_secret_hash() {
+        let strip = |s| {
+            let microtype: MicrotypeMacro = parse_str(s).unwrap();
+            strip_special_attrs(microtype.0[0].attrs.clone()).map(|(_, special)| special.secret)
+        };
+
+        let secret = strip("#[secret(hash)] String { Token }").unwrap().unwrap();
+        assert!(secret.hash.is_some());
+        assert!(secret.serialize.is_none());
+
+        let secret = strip("#[secret(serialize, hash)] String { Token }")
+            .unwrap()
+            .unwrap();
+        assert!(secret.hash.is_some());
+        assert!(secret.serialize.is_some());
+
+        for s in [
+            "#[secret(hash, hash)] String { Token }",
+            "#[secret(hashed)] String { Token }",
+            "#[secret(hash = true)] String { Token }",
+        ] {
+            assert!(strip(s).is_err());
+        }
+    }
 }
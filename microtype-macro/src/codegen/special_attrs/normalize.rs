@@ -0,0 +1,114 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Attribute, Path};
+
+use super::{Normalization, SpecialAttrs, StringAttr, TypeAnnotation};
+
+fn duplicate_normalize(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `normalize` attribute found"))
+}
+
+/// Returns the path of the normalizer in `#[normalize(path::to::fn)]`, if present
+pub fn strip_normalize_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<Path>), TokenStream> {
+    let (normalize, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("normalize"));
+
+    let normalize = match &normalize[..] {
+        [] => None,
+        [single] => match single.parse_args() {
+            Ok(normalizer) => Some(normalizer),
+            Err(e) => return Err(e.to_compile_error()),
+        },
+        [_, second, ..] => return Err(duplicate_normalize(second.span())),
+    };
+
+    Ok((attrs, normalize))
+}
+
+/// The steps from `#[string(normalize = [...])]`, if any
+fn string_steps(special_attrs: &SpecialAttrs) -> &[Normalization] {
+    match &special_attrs.type_annotation {
+        Some(TypeAnnotation::String(StringAttr { normalize, .. })) => normalize,
+        _ => &[],
+    }
+}
+
+/// Whether values are normalized when they're created
+pub fn normalizes(special_attrs: &SpecialAttrs) -> bool {
+    special_attrs.normalize.is_some() || !string_steps(special_attrs).is_empty()
+}
+
+/// Statements that replace `inner` with its normalized form
+///
+/// This is used in `Microtype::new` and `ValidatedMicrotype::try_new`, which every other
+/// constructor (e.g. `FromStr`, `Deserialize`) goes through. The built-in steps run in order,
+/// followed by the `#[normalize(...)]` function
+pub fn normalize_inner(special_attrs: &SpecialAttrs) -> TokenStream {
+    let mut steps = TokenStream::new();
+
+    for step in string_steps(special_attrs) {
+        steps.extend(match step {
+            Normalization::Trim => quote! {
+                let end = inner.trim_end().len();
+                inner.truncate(end);
+                let start = inner.len() - inner.trim_start().len();
+                inner.drain(..start);
+            },
+            Normalization::Lowercase => quote! {
+                inner = inner.to_lowercase();
+            },
+            Normalization::Nfc => quote! {
+                inner = ::microtype::__private::unicode_normalization::UnicodeNormalization::nfc(
+                    inner.as_str(),
+                )
+                .collect();
+            },
+        });
+    }
+
+    if let Some(normalizer) = &special_attrs.normalize {
+        steps.extend(quote! {
+            inner = #normalizer(inner);
+        });
+    }
+
+    match steps.is_empty() {
+        true => quote! {},
+        false => quote! {
+            let mut inner = inner;
+            #steps
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+    use syn::parse_str;
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    #[test]
+    fn strips_normalize_attr() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[derive(Foo)] #[normalize(foo::canonical)] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (attrs, normalize) = strip_normalize_attr(attrs).unwrap();
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(
+            normalize.unwrap().to_token_stream().to_string(),
+            "foo :: canonical"
+        );
+
+        let microtype: MicrotypeMacro =
+            parse_str("#[normalize(a)] #[normalize(b)] String { Email }").unwrap();
+        strip_normalize_attr(microtype.0[0].attrs.clone()).unwrap_err();
+    }
+}
@@ -0,0 +1,196 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{punctuated::Punctuated, spanned::Spanned, Attribute, Generics, Ident, LitStr, Token};
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[one_of(\"a\", \"b\", ...)]`"))
+}
+
+fn duplicate_one_of(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `one_of` attribute found"))
+}
+
+fn duplicate_value(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate value in `one_of` attribute"))
+}
+
+fn no_const_name(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("values must contain at least one ASCII letter or digit, to be used as the name of the associated constant"))
+}
+
+fn reserved_const(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("the associated constant `ALL` is reserved for the list of values"))
+}
+
+fn duplicate_const(span: Span, name: &str) -> TokenStream {
+    let message = format!("more than one value would have the associated constant `{name}`");
+    quote_spanned!(span => compile_error!(#message))
+}
+
+/// The contents of a `#[one_of("a", "b", ...)]` attribute
+#[derive(Debug)]
+pub struct OneOfAttr {
+    pub values: Vec<LitStr>,
+    /// The span of `one_of`
+    pub span: Span,
+}
+
+/// The name of the associated constant for `value`, e.g. `EN_GB` for `"en-GB"`
+///
+/// Anything that can't appear in an identifier becomes `_`, and a leading `_` is added if the
+/// value starts with a digit. There's no name if the value doesn't have any ASCII letters or digits
+fn const_name(value: &str) -> Option<String> {
+    if !value.chars().any(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let name: String = value
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect();
+
+    match name.starts_with(|c: char| c.is_ascii_digit()) {
+        true => Some(format!("_{name}")),
+        false => Some(name),
+    }
+}
+
+pub fn strip_one_of_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<OneOfAttr>), TokenStream> {
+    let (one_of, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("one_of"));
+
+    let attr = match &one_of[..] {
+        [] => return Ok((attrs, None)),
+        [single] => single,
+        [_, second, ..] => return Err(duplicate_one_of(second.span())),
+    };
+
+    let values = match attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated) {
+        Ok(values) if !values.is_empty() => values,
+        Ok(_) => return Err(generic_err(attr.span())),
+        Err(e) => return Err(generic_err(e.span())),
+    };
+
+    let mut seen_values = vec![];
+    let mut seen_names = vec![];
+    for value in &values {
+        let name = match const_name(&value.value()) {
+            Some(name) => name,
+            None => return Err(no_const_name(value.span())),
+        };
+        if name == "ALL" {
+            return Err(reserved_const(value.span()));
+        }
+        if seen_values.contains(&value.value()) {
+            return Err(duplicate_value(value.span()));
+        }
+        if seen_names.contains(&name) {
+            return Err(duplicate_const(value.span(), &name));
+        }
+        seen_values.push(value.value());
+        seen_names.push(name);
+    }
+
+    let one_of = OneOfAttr {
+        values: values.into_iter().collect(),
+        span: attr.path.span(),
+    };
+
+    Ok((attrs, Some(one_of)))
+}
+
+/// Associated constants for each value, and `ALL`
+///
+/// The constants are `&'static str`, since a `String` can't be created in a `const` context. This
+/// also means they can be used as patterns when matching on `as_ref()`
+pub fn one_of_impl(name: &Ident, generics: &Generics, one_of: &OneOfAttr) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let names: Vec<_> = one_of
+        .values
+        .iter()
+        .map(|value| {
+            let name = const_name(&value.value()).expect("checked when the attribute is stripped");
+            Ident::new(&name, value.span())
+        })
+        .collect();
+    let values = &one_of.values;
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(
+                pub const #names: &'static ::core::primitive::str = #values;
+            )*
+
+            /// Every allowed value, in the order they were declared
+            pub const ALL: &'static [&'static ::core::primitive::str] = &[#(Self::#names),*];
+        }
+    }
+}
+
+/// Statements for `ValidatedMicrotype::try_new` that reject values that aren't in the list
+pub fn one_of_check(name: &Ident) -> TokenStream {
+    let microtype = name.to_string();
+
+    quote! {
+        if !Self::ALL.contains(&::core::convert::AsRef::<::core::primitive::str>::as_ref(&inner)) {
+            return Err(::microtype::OneOfError {
+                microtype: #microtype,
+                allowed: Self::ALL,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    fn strip(s: &str) -> Result<Option<OneOfAttr>, TokenStream> {
+        let microtype: MicrotypeMacro = parse_str(s).unwrap();
+        strip_one_of_attr(microtype.0[0].attrs.clone()).map(|(_, one_of)| one_of)
+    }
+
+    #[test]
+    fn strips_one_of_attr() {
+        let one_of = strip(r#"#[one_of("USD", "EUR", "GBP")] String { Currency }"#)
+            .unwrap()
+            .unwrap();
+        let values: Vec<_> = one_of.values.iter().map(LitStr::value).collect();
+        assert_eq!(values, ["USD", "EUR", "GBP"]);
+
+        assert!(strip("String { Currency }").unwrap().is_none());
+
+        for s in [
+            r#"#[one_of()] String { Currency }"#,
+            r#"#[one_of(USD)] String { Currency }"#,
+            r#"#[one_of("USD", "USD")] String { Currency }"#,
+            r#"#[one_of("en-GB", "en_GB")] String { Locale }"#,
+            r#"#[one_of("USD", "")] String { Currency }"#,
+            r#"#[one_of("some", "all")] String { Scope }"#,
+            r#"#[one_of("USD")] #[one_of("EUR")] String { Currency }"#,
+        ] {
+            strip(s).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn generates_const_names() {
+        assert_eq!(const_name("USD").unwrap(), "USD");
+        assert_eq!(const_name("en-GB").unwrap(), "EN_GB");
+        assert_eq!(const_name("in progress").unwrap(), "IN_PROGRESS");
+        assert_eq!(const_name("1st").unwrap(), "_1ST");
+        assert_eq!(const_name("_x").unwrap(), "_X");
+        assert_eq!(const_name(""), None);
+        assert_eq!(const_name("ü-ß"), None);
+    }
+}
@@ -0,0 +1,619 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Generics, Ident, Type};
+
+use crate::codegen::generics::{with_param, with_predicates};
+
+use super::{helpers::is_signed_primitive, FloatAttr, IntAttr, Overflow};
+
+const ARITHMETIC_OPS: [(&str, &str); 5] = [
+    ("Add", "add"),
+    ("Sub", "sub"),
+    ("Mul", "mul"),
+    ("Div", "div"),
+    ("Rem", "rem"),
+];
+
+const BIT_OPS: [(&str, &str); 3] = [
+    ("BitAnd", "bitand"),
+    ("BitOr", "bitor"),
+    ("BitXor", "bitxor"),
+];
+
+const SHIFT_OPS: [(&str, &str); 2] = [("Shl", "shl"), ("Shr", "shr")];
+
+/// The trait and method idents for an operator, e.g. `("Add", "add")` gives `Add` and `add`, or
+/// `AddAssign` and `add_assign` if `assign` is true
+fn op_idents(name: &Ident, (op, method): (&str, &str), assign: bool) -> (Ident, Ident) {
+    match assign {
+        false => (Ident::new(op, name.span()), Ident::new(method, name.span())),
+        true => (
+            Ident::new(&format!("{op}Assign"), name.span()),
+            Ident::new(&format!("{method}_assign"), name.span()),
+        ),
+    }
+}
+
+/// The inherent method that replaces `method` under `overflow`, e.g. `wrapping_add` for `add`
+///
+/// There's no `saturating_rem`, since the remainder can't overflow (apart from `MIN % -1`, which
+/// panics anyway)
+fn overflow_method(method: &Ident, overflow: Option<Overflow>) -> Option<Ident> {
+    match overflow {
+        None => None,
+        Some(Overflow::Saturating) if method == "rem" => None,
+        Some(overflow) => Some(Ident::new(
+            &format!("{}_{method}", overflow.prefix()),
+            method.span(),
+        )),
+    }
+}
+
+/// Which right hand sides operators are implemented for, on top of `T`
+#[derive(Debug, Clone, Copy)]
+struct Operands {
+    /// `T op T`, which is false if `#[ops(...)]` replaces it
+    same_type: bool,
+    scalar: bool,
+    refs: bool,
+}
+
+/// How operators handle overflow, and how the result is turned back into the microtype
+#[derive(Debug, Clone, Copy)]
+struct Mode {
+    overflow: Option<Overflow>,
+    /// Results go through `ValidatedMicrotype::try_new`, so may be rejected (e.g. out of range, or
+    /// not finite)
+    bounded: bool,
+}
+
+impl Mode {
+    /// Whether operators return `Option<T>`
+    fn fallible(self) -> bool {
+        self.bounded || self.overflow == Some(Overflow::Checked)
+    }
+}
+
+/// The type an operator returns
+fn output(name: &Ident, generics: &Generics, mode: Mode) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    match mode.fallible() {
+        true => quote!(::core::option::Option<#name #ty_generics>),
+        false => quote!(#name #ty_generics),
+    }
+}
+
+/// Wrap an inner `value` in the microtype, which is `None` if bounded and out of range
+fn construct(name: &Ident, generics: &Generics, value: TokenStream, bounded: bool) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    match bounded {
+        true => {
+            quote!(<#name #ty_generics as ::microtype::ValidatedMicrotype>::try_new(#value).ok())
+        }
+        false => quote!(<#name #ty_generics as ::microtype::Microtype>::new(#value)),
+    }
+}
+
+/// Turn an `Option` of an inner value into an `Option` of the microtype, see [`construct`]
+fn construct_checked(name: &Ident, generics: &Generics, bounded: bool) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    match bounded {
+        true => quote! {
+            .and_then(|value| <#name #ty_generics as ::microtype::ValidatedMicrotype>::try_new(value).ok())
+        },
+        false => quote!(.map(<#name #ty_generics as ::microtype::Microtype>::new)),
+    }
+}
+
+/// An expression of type [`output`], applying the operator to the inner values in `args`
+fn apply(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    (op, method): (&Ident, &Ident),
+    mode: Mode,
+    args: TokenStream,
+) -> TokenStream {
+    match (mode.overflow, overflow_method(method, mode.overflow)) {
+        (Some(Overflow::Checked), Some(checked)) => {
+            let construct = construct_checked(name, generics, mode.bounded);
+            quote!(<#inner>::#checked(#args) #construct)
+        }
+        (_, Some(overflow_method)) => construct(
+            name,
+            generics,
+            quote!(<#inner>::#overflow_method(#args)),
+            mode.bounded,
+        ),
+        (_, None) => construct(
+            name,
+            generics,
+            quote!(<#inner as ::core::ops::#op>::#method(#args)),
+            mode.bounded,
+        ),
+    }
+}
+
+/// The body of an assignment operator, or `None` if there shouldn't be one (fallible operators
+/// don't return `Self`, so can't be used to assign)
+fn apply_assign(
+    inner: &Type,
+    (assign_op, assign_method): (&Ident, &Ident),
+    method: &Ident,
+    mode: Mode,
+    rhs: TokenStream,
+) -> Option<TokenStream> {
+    if mode.fallible() {
+        return None;
+    }
+
+    match overflow_method(method, mode.overflow) {
+        Some(overflow_method) => Some(quote! {
+            self.0 = <#inner>::#overflow_method(self.0, #rhs);
+        }),
+        None => Some(quote! {
+            <#inner as ::core::ops::#assign_op>::#assign_method(&mut self.0, #rhs)
+        }),
+    }
+}
+
+/// `T op T` and `T op= T`, as well as the reference and scalar variants if enabled
+///
+/// The reference variants are skipped along with `T op T`, since they would have a different
+/// `Output` to the `#[ops(...)]` impl that replaces it
+fn binary_op_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    op: (&str, &str),
+    operands: Operands,
+    mode: Mode,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (assign_op, assign_method) = op_idents(name, op, true);
+    let (op, method) = op_idents(name, op, false);
+    let output = output(name, generics, mode);
+    let apply = |args| apply(name, generics, inner, (&op, &method), mode, args);
+    let apply_assign = |rhs| apply_assign(inner, (&assign_op, &assign_method), &method, mode, rhs);
+
+    let op_generics = with_predicates(
+        generics,
+        [parse_quote!(#inner: ::core::ops::#op<Output = #inner>)],
+    );
+    let (op_impl_generics, _, op_where_clause) = op_generics.split_for_impl();
+    let assign_generics =
+        with_predicates(generics, [parse_quote!(#inner: ::core::ops::#assign_op)]);
+    let (assign_impl_generics, _, assign_where_clause) = assign_generics.split_for_impl();
+    // the inner value has to be copied out from behind the reference
+    let ref_op_generics = with_param(
+        &with_predicates(
+            generics,
+            [parse_quote!(#inner: ::core::marker::Copy + ::core::ops::#op<Output = #inner>)],
+        ),
+        parse_quote!('__a),
+    );
+    let (ref_op_impl_generics, _, ref_op_where_clause) = ref_op_generics.split_for_impl();
+    let ref_assign_generics = with_param(
+        &with_predicates(
+            generics,
+            [parse_quote!(#inner: ::core::marker::Copy + ::core::ops::#assign_op)],
+        ),
+        parse_quote!('__a),
+    );
+    let (ref_assign_impl_generics, _, ref_assign_where_clause) =
+        ref_assign_generics.split_for_impl();
+
+    let mut tokens = TokenStream::new();
+
+    if operands.same_type {
+        let body = apply(quote!(self.0, rhs.0));
+        tokens.extend(quote! {
+            impl #op_impl_generics ::core::ops::#op for #name #ty_generics #op_where_clause {
+                type Output = #output;
+                fn #method(self, rhs: Self) -> Self::Output {
+                    #body
+                }
+            }
+        });
+
+        if let Some(body) = apply_assign(quote!(rhs.0)) {
+            tokens.extend(quote! {
+                impl #assign_impl_generics ::core::ops::#assign_op for #name #ty_generics #assign_where_clause {
+                    fn #assign_method(&mut self, rhs: Self) {
+                        #body
+                    }
+                }
+            });
+        }
+    }
+
+    if operands.same_type && operands.refs {
+        let body = apply(quote!(self.0, rhs.0));
+        tokens.extend(quote! {
+            impl #ref_op_impl_generics ::core::ops::#op<&'__a #name #ty_generics> for #name #ty_generics #ref_op_where_clause {
+                type Output = #output;
+                fn #method(self, rhs: &'__a #name #ty_generics) -> Self::Output {
+                    #body
+                }
+            }
+
+            impl #ref_op_impl_generics ::core::ops::#op<&'__a #name #ty_generics> for &'__a #name #ty_generics #ref_op_where_clause {
+                type Output = #output;
+                fn #method(self, rhs: &'__a #name #ty_generics) -> Self::Output {
+                    #body
+                }
+            }
+        });
+
+        if let Some(body) = apply_assign(quote!(rhs.0)) {
+            tokens.extend(quote! {
+                impl #ref_assign_impl_generics ::core::ops::#assign_op<&'__a #name #ty_generics> for #name #ty_generics #ref_assign_where_clause {
+                    fn #assign_method(&mut self, rhs: &'__a #name #ty_generics) {
+                        #body
+                    }
+                }
+            });
+        }
+    }
+
+    if operands.scalar {
+        let body = apply(quote!(self.0, rhs));
+        tokens.extend(quote! {
+            impl #op_impl_generics ::core::ops::#op<#inner> for #name #ty_generics #op_where_clause {
+                type Output = #output;
+                fn #method(self, rhs: #inner) -> Self::Output {
+                    #body
+                }
+            }
+        });
+
+        if let Some(body) = apply_assign(quote!(rhs)) {
+            tokens.extend(quote! {
+                impl #assign_impl_generics ::core::ops::#assign_op<#inner> for #name #ty_generics #assign_where_clause {
+                    fn #assign_method(&mut self, rhs: #inner) {
+                        #body
+                    }
+                }
+            });
+        }
+    }
+
+    tokens
+}
+
+/// `op T`, and `op &T` if reference ops are enabled
+fn unary_op_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    op: (&str, &str),
+    operands: Operands,
+    mode: Mode,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (op, method) = op_idents(name, op, false);
+    let output = output(name, generics, mode);
+    let body = apply(name, generics, inner, (&op, &method), mode, quote!(self.0));
+
+    let op_generics = with_predicates(
+        generics,
+        [parse_quote!(#inner: ::core::ops::#op<Output = #inner>)],
+    );
+    let (impl_generics, _, where_clause) = op_generics.split_for_impl();
+
+    let mut tokens = quote! {
+        impl #impl_generics ::core::ops::#op for #name #ty_generics #where_clause {
+            type Output = #output;
+            fn #method(self) -> Self::Output {
+                #body
+            }
+        }
+    };
+
+    if operands.refs {
+        let ref_generics = with_param(
+            &with_predicates(
+                generics,
+                [parse_quote!(#inner: ::core::marker::Copy + ::core::ops::#op<Output = #inner>)],
+            ),
+            parse_quote!('__a),
+        );
+        let (impl_generics, _, where_clause) = ref_generics.split_for_impl();
+
+        tokens.extend(quote! {
+            impl #impl_generics ::core::ops::#op for &'__a #name #ty_generics #where_clause {
+                type Output = #output;
+                fn #method(self) -> Self::Output {
+                    #body
+                }
+            }
+        });
+    }
+
+    tokens
+}
+
+/// Shifts are generic over the right hand side, since e.g. `u64` can be shifted by any integer
+fn shift_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    op: (&str, &str),
+    bounded: bool,
+) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (assign_op, assign_method) = op_idents(name, op, true);
+    let (op, method) = op_idents(name, op, false);
+    let output = output(
+        name,
+        generics,
+        Mode {
+            overflow: None,
+            bounded,
+        },
+    );
+    let body = construct(
+        name,
+        generics,
+        quote!(<#inner as ::core::ops::#op<__R>>::#method(self.0, rhs)),
+        bounded,
+    );
+
+    let op_generics = with_param(
+        &with_predicates(
+            generics,
+            [parse_quote!(#inner: ::core::ops::#op<__R, Output = #inner>)],
+        ),
+        parse_quote!(__R),
+    );
+    let (op_impl_generics, _, op_where_clause) = op_generics.split_for_impl();
+    let assign_generics = with_param(
+        &with_predicates(
+            generics,
+            [parse_quote!(#inner: ::core::ops::#assign_op<__R>)],
+        ),
+        parse_quote!(__R),
+    );
+    let (assign_impl_generics, _, assign_where_clause) = assign_generics.split_for_impl();
+
+    let mut tokens = quote! {
+        impl #op_impl_generics ::core::ops::#op<__R> for #name #ty_generics #op_where_clause {
+            type Output = #output;
+            fn #method(self, rhs: __R) -> Self::Output {
+                #body
+            }
+        }
+    };
+
+    if !bounded {
+        tokens.extend(quote! {
+            impl #assign_impl_generics ::core::ops::#assign_op<__R> for #name #ty_generics #assign_where_clause {
+                fn #assign_method(&mut self, rhs: __R) {
+                    <#inner as ::core::ops::#assign_op<__R>>::#assign_method(&mut self.0, rhs)
+                }
+            }
+        });
+    }
+
+    tokens
+}
+
+/// `Sum` and `Product`, over both values and references
+///
+/// With an overflow mode, these fold using the same method as `Add` and `Mul`, and fallible ones
+/// are implemented for `Option<T>` instead
+fn iter_impls(name: &Ident, generics: &Generics, inner: &Type, mode: Mode) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let mut tokens = TokenStream::new();
+    let output = output(name, generics, mode);
+
+    for (op, method, arithmetic_method, identity) in [
+        ("Sum", "sum", "add", quote!(0)),
+        ("Product", "product", "mul", quote!(1)),
+    ] {
+        let (op, method) = op_idents(name, (op, method), false);
+        let arithmetic_method = Ident::new(arithmetic_method, name.span());
+
+        let body = match (
+            mode.overflow,
+            overflow_method(&arithmetic_method, mode.overflow),
+        ) {
+            (Some(Overflow::Checked), Some(checked)) => {
+                let construct = construct_checked(name, generics, mode.bounded);
+                quote! {
+                    iter.map(|item| item.0)
+                        .try_fold(#identity, <#inner>::#checked)
+                        #construct
+                }
+            }
+            (_, Some(overflow_method)) => {
+                let result = construct(name, generics, quote!(value), mode.bounded);
+                quote! {
+                    let value = iter
+                        .map(|item| item.0)
+                        .fold(#identity, <#inner>::#overflow_method);
+                    #result
+                }
+            }
+            (_, None) => {
+                let result = construct(name, generics, quote!(value), mode.bounded);
+                quote! {
+                    let value = <#inner as ::core::iter::#op>::#method(iter.map(|item| item.0));
+                    #result
+                }
+            }
+        };
+
+        let op_generics = with_predicates(generics, [parse_quote!(#inner: ::core::iter::#op)]);
+        let (impl_generics, _, where_clause) = op_generics.split_for_impl();
+        let ref_generics = with_param(
+            &with_predicates(
+                generics,
+                [parse_quote!(#inner: ::core::marker::Copy + ::core::iter::#op)],
+            ),
+            parse_quote!('__a),
+        );
+        let (ref_impl_generics, _, ref_where_clause) = ref_generics.split_for_impl();
+
+        tokens.extend(quote! {
+            impl #impl_generics ::core::iter::#op<#name #ty_generics> for #output #where_clause {
+                fn #method<__I: ::core::iter::Iterator<Item = #name #ty_generics>>(iter: __I) -> Self {
+                    #body
+                }
+            }
+
+            impl #ref_impl_generics ::core::iter::#op<&'__a #name #ty_generics> for #output #ref_where_clause {
+                fn #method<__I: ::core::iter::Iterator<Item = &'__a #name #ty_generics>>(iter: __I) -> Self {
+                    #body
+                }
+            }
+        });
+    }
+
+    tokens
+}
+
+/// `replaced` are the traits (e.g. `"Mul"`) that `#[ops(...)]` implements between `T` and itself
+pub fn ops_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    int: &IntAttr,
+    replaced: &[&str],
+) -> TokenStream {
+    let bounded = int.range.is_some();
+    // bounded microtypes always use checked arithmetic, since the result has to be checked anyway
+    let arithmetic = Mode {
+        overflow: match bounded {
+            true => Some(Overflow::Checked),
+            false => int.overflow,
+        },
+        bounded,
+    };
+    // bitwise operators can't overflow, but can still go out of range
+    let bitwise = Mode {
+        overflow: None,
+        bounded,
+    };
+
+    let operands = Operands {
+        same_type: true,
+        scalar: int.scalar_ops,
+        refs: int.ref_ops,
+    };
+
+    let mut tokens = TokenStream::new();
+
+    for op in ARITHMETIC_OPS {
+        let operands = Operands {
+            same_type: !replaced.contains(&op.0),
+            ..operands
+        };
+        tokens.extend(binary_op_impls(
+            name, generics, inner, op, operands, arithmetic,
+        ));
+    }
+
+    if int.neg.unwrap_or_else(|| is_signed_primitive(inner)) {
+        tokens.extend(unary_op_impls(
+            name,
+            generics,
+            inner,
+            ("Neg", "neg"),
+            operands,
+            arithmetic,
+        ));
+    }
+
+    if int.bit_ops {
+        for op in BIT_OPS {
+            tokens.extend(binary_op_impls(
+                name, generics, inner, op, operands, bitwise,
+            ));
+        }
+        for op in SHIFT_OPS {
+            tokens.extend(shift_impls(name, generics, inner, op, bounded));
+        }
+        tokens.extend(unary_op_impls(
+            name,
+            generics,
+            inner,
+            ("Not", "not"),
+            operands,
+            bitwise,
+        ));
+    }
+
+    if int.iter {
+        tokens.extend(iter_impls(name, generics, inner, arithmetic));
+    }
+
+    tokens
+}
+
+/// Floats don't overflow, but finite microtypes reject NaN and infinity, so their operators return
+/// `Option<T>` like bounded ints
+pub fn float_ops_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    float: &FloatAttr,
+    replaced: &[&str],
+) -> TokenStream {
+    let mode = Mode {
+        overflow: None,
+        bounded: float.finite,
+    };
+    let operands = Operands {
+        same_type: true,
+        scalar: float.scalar_ops,
+        refs: float.ref_ops,
+    };
+
+    let mut tokens = TokenStream::new();
+
+    for op in ARITHMETIC_OPS {
+        let operands = Operands {
+            same_type: !replaced.contains(&op.0),
+            ..operands
+        };
+        tokens.extend(binary_op_impls(name, generics, inner, op, operands, mode));
+    }
+
+    tokens.extend(unary_op_impls(
+        name,
+        generics,
+        inner,
+        ("Neg", "neg"),
+        operands,
+        mode,
+    ));
+
+    if float.iter {
+        tokens.extend(iter_impls(name, generics, inner, mode));
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_overflow_method() {
+        let add = parse_quote!(add);
+        let rem = parse_quote!(rem);
+
+        assert_eq!(overflow_method(&add, None), None);
+        assert_eq!(
+            overflow_method(&add, Some(Overflow::Wrapping)).unwrap(),
+            "wrapping_add"
+        );
+        assert_eq!(
+            overflow_method(&rem, Some(Overflow::Checked)).unwrap(),
+            "checked_rem"
+        );
+        assert_eq!(overflow_method(&rem, Some(Overflow::Saturating)), None);
+    }
+}
@@ -0,0 +1,147 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{spanned::Spanned, Attribute, Lit, Meta, NestedMeta};
+
+use crate::codegen::{HAS_DEREF_IMPLS, HAS_SERDE, HAS_TEST_IMPLS};
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[microtype(key = bool, ...)]`, where `key` is one of `serde`, `deref`, `test_impls`"))
+}
+
+fn duplicate_key(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate key in `microtype` attribute"))
+}
+
+/// Per-type overrides for the crate-wide `serde`, `deref_impls` and `test_impls` features
+///
+/// `None` means the feature flag decides
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FeatureOverrides {
+    pub serde: Option<bool>,
+    pub deref: Option<bool>,
+    pub test_impls: Option<bool>,
+}
+
+impl FeatureOverrides {
+    pub fn serde(&self) -> bool {
+        self.serde.unwrap_or(HAS_SERDE)
+    }
+
+    pub fn deref(&self) -> bool {
+        self.deref.unwrap_or(HAS_DEREF_IMPLS)
+    }
+
+    pub fn test_impls(&self) -> bool {
+        self.test_impls.unwrap_or(HAS_TEST_IMPLS)
+    }
+}
+
+/// Attributes on a single microtype come before the attributes on its block, so if a key is set
+/// in both, the first one wins
+pub fn strip_feature_overrides(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, FeatureOverrides), TokenStream> {
+    let (overrides, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("microtype"));
+
+    let mut result = FeatureOverrides::default();
+
+    for attr in overrides {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(other) => return Err(generic_err(other.span())),
+            Err(e) => return Err(e.to_compile_error()),
+        };
+
+        let mut seen = FeatureOverrides::default();
+
+        for nested in list.nested {
+            let (path, value) = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => match name_value.lit {
+                    Lit::Bool(b) => (name_value.path, b.value),
+                    other => return Err(generic_err(other.span())),
+                },
+                other => return Err(generic_err(other.span())),
+            };
+
+            let (seen, result) = match path.get_ident() {
+                Some(ident) if ident == "serde" => (&mut seen.serde, &mut result.serde),
+                Some(ident) if ident == "deref" => (&mut seen.deref, &mut result.deref),
+                Some(ident) if ident == "test_impls" => {
+                    (&mut seen.test_impls, &mut result.test_impls)
+                }
+                _ => return Err(generic_err(path.span())),
+            };
+
+            if seen.replace(value).is_some() {
+                return Err(duplicate_key(path.span()));
+            }
+
+            result.get_or_insert(value);
+        }
+    }
+
+    Ok((attrs, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::{model::flatten, parse::MicrotypeMacro};
+
+    use super::*;
+
+    #[test]
+    fn strips_overrides() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[derive(Foo)] #[microtype(deref = false, serde = true)] String { Email }")
+                .unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (attrs, overrides) = strip_feature_overrides(attrs).unwrap();
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(
+            overrides,
+            FeatureOverrides {
+                serde: Some(true),
+                deref: Some(false),
+                test_impls: None,
+            }
+        );
+    }
+
+    #[test]
+    fn microtype_overrides_take_precedence_over_block() {
+        let microtype: MicrotypeMacro = parse_str(
+            "#[microtype(deref = false, serde = true)] String { #[microtype(deref = true)] Email }",
+        )
+        .unwrap();
+        let microtypes = flatten(microtype);
+
+        let (_, overrides) = strip_feature_overrides(microtypes[0].attrs.clone()).unwrap();
+
+        assert_eq!(overrides.deref, Some(true));
+        assert_eq!(overrides.serde, Some(true));
+    }
+
+    #[test]
+    fn fails_on_duplicate_key() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[microtype(deref = false, deref = true)] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        strip_feature_overrides(attrs).unwrap_err();
+    }
+
+    #[test]
+    fn fails_on_unknown_key() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[microtype(foo = false)] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        strip_feature_overrides(attrs).unwrap_err();
+    }
+}
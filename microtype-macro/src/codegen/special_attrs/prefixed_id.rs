@@ -0,0 +1,234 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Attribute, Generics, Ident, LitStr, Token,
+};
+
+use super::helpers::string_serde_impls;
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[prefixed_id(\"prefix\")]` or `#[prefixed_id(\"prefix\", stored)]`"))
+}
+
+fn duplicate_prefixed_id(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `prefixed_id` attribute found"))
+}
+
+fn empty_prefix(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("the prefix must not be empty"))
+}
+
+/// The contents of a `#[prefixed_id("prefix")]` or `#[prefixed_id("prefix", stored)]` attribute
+#[derive(Debug)]
+pub struct PrefixedIdAttr {
+    /// The prefix, without the `_` separator
+    pub prefix: LitStr,
+    /// The inner value includes the prefix, rather than just the part after it
+    pub stored: bool,
+    /// The span of `prefixed_id`
+    pub span: Span,
+}
+
+struct PrefixedIdArgs {
+    prefix: LitStr,
+    stored: bool,
+}
+
+impl Parse for PrefixedIdArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let prefix = input.parse()?;
+
+        if input.is_empty() {
+            return Ok(Self {
+                prefix,
+                stored: false,
+            });
+        }
+
+        let _: Token![,] = input.parse()?;
+        let key: Ident = input.parse()?;
+        if key != "stored" {
+            return Err(syn::Error::new(key.span(), "expected `stored`"));
+        }
+        let _: Option<Token![,]> = input.parse()?;
+
+        Ok(Self {
+            prefix,
+            stored: true,
+        })
+    }
+}
+
+pub fn strip_prefixed_id_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<PrefixedIdAttr>), TokenStream> {
+    let (prefixed_id, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("prefixed_id"));
+
+    let attr = match &prefixed_id[..] {
+        [] => return Ok((attrs, None)),
+        [single] => single,
+        [_, second, ..] => return Err(duplicate_prefixed_id(second.span())),
+    };
+
+    let PrefixedIdArgs { prefix, stored } = match attr.parse_args() {
+        Ok(args) => args,
+        Err(e) => return Err(generic_err(e.span())),
+    };
+    if prefix.value().is_empty() {
+        return Err(empty_prefix(prefix.span()));
+    }
+
+    let prefixed_id = PrefixedIdAttr {
+        prefix,
+        stored,
+        span: attr.path.span(),
+    };
+
+    Ok((attrs, Some(prefixed_id)))
+}
+
+/// `usr_` for `#[prefixed_id("usr")]`
+fn full_prefix(prefixed_id: &PrefixedIdAttr) -> LitStr {
+    let prefix = format!("{}_", prefixed_id.prefix.value());
+    LitStr::new(&prefix, prefixed_id.prefix.span())
+}
+
+/// Statements for `ValidatedMicrotype::try_new` that check the inner value
+///
+/// A stored prefix must be present, followed by a non-empty ID. Otherwise, the inner value is just
+/// the ID, which must be non-empty and must not start with the prefix, since `Display` adds it
+/// again and `FromStr` would only strip it once
+pub fn prefixed_id_check(name: &Ident, prefixed_id: &PrefixedIdAttr) -> TokenStream {
+    let microtype = name.to_string();
+    let prefix = &prefixed_id.prefix;
+    let full_prefix = full_prefix(prefixed_id);
+    let invalid = match prefixed_id.stored {
+        true => quote!(inner.strip_prefix(#full_prefix).unwrap_or_default().is_empty()),
+        false => quote!(inner.is_empty() || inner.starts_with(#full_prefix)),
+    };
+
+    quote! {
+        if #invalid {
+            return Err(::microtype::PrefixError {
+                microtype: #microtype,
+                prefix: #prefix,
+            });
+        }
+    }
+}
+
+/// `PREFIX`, `Display`, `FromStr` and `AsRef<str>`, plus the serde impls if `serde` is true
+///
+/// The prefixed form is used everywhere except the inner value (and therefore `FromSql` and
+/// `ToSql`), so the serde impls use `Display` and `FromStr` rather than being `transparent`
+pub fn prefixed_id_impls(
+    name: &Ident,
+    generics: &Generics,
+    prefixed_id: &PrefixedIdAttr,
+    serde: bool,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let microtype = name.to_string();
+    let prefix = &prefixed_id.prefix;
+    let full_prefix = full_prefix(prefixed_id);
+    let string = quote!(::microtype::__private::alloc::string::String);
+
+    let (display, from_str) = match prefixed_id.stored {
+        true => (
+            quote!(::core::fmt::Display::fmt(self.0.as_str(), f)),
+            quote! {
+                <Self as ::microtype::ValidatedMicrotype>::try_new(#string::from(s))
+            },
+        ),
+        false => (
+            quote!(::core::write!(f, "{}{}", #full_prefix, self.0)),
+            quote! {
+                match s.strip_prefix(#full_prefix) {
+                    ::core::option::Option::Some(id) => {
+                        <Self as ::microtype::ValidatedMicrotype>::try_new(#string::from(id))
+                    }
+                    ::core::option::Option::None => Err(::microtype::PrefixError {
+                        microtype: #microtype,
+                        prefix: #prefix,
+                    }),
+                }
+            },
+        ),
+    };
+
+    let serde_impls = serde.then(|| string_serde_impls(name, generics));
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The prefix, without the `_` separator
+            pub const PREFIX: &'static ::core::primitive::str = #prefix;
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                #display
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::microtype::PrefixError;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                #from_str
+            }
+        }
+
+        impl #impl_generics ::core::convert::AsRef<::core::primitive::str> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &::core::primitive::str {
+                &self.0
+            }
+        }
+
+        #serde_impls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    fn strip(s: &str) -> Result<Option<PrefixedIdAttr>, TokenStream> {
+        let microtype: MicrotypeMacro = parse_str(s).unwrap();
+        strip_prefixed_id_attr(microtype.0[0].attrs.clone()).map(|(_, prefixed_id)| prefixed_id)
+    }
+
+    #[test]
+    fn strips_prefixed_id_attr() {
+        let prefixed_id = strip(r#"#[prefixed_id("usr")] String { UserId }"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(prefixed_id.prefix.value(), "usr");
+        assert!(!prefixed_id.stored);
+        assert_eq!(full_prefix(&prefixed_id).value(), "usr_");
+
+        let prefixed_id = strip(r#"#[prefixed_id("ord", stored)] String { OrderId }"#)
+            .unwrap()
+            .unwrap();
+        assert!(prefixed_id.stored);
+
+        assert!(strip("String { UserId }").unwrap().is_none());
+
+        for s in [
+            r#"#[prefixed_id] String { UserId }"#,
+            r#"#[prefixed_id("")] String { UserId }"#,
+            r#"#[prefixed_id(usr)] String { UserId }"#,
+            r#"#[prefixed_id("usr", stripped)] String { UserId }"#,
+            r#"#[prefixed_id("usr")] #[prefixed_id("ord")] String { UserId }"#,
+        ] {
+            strip(s).unwrap_err();
+        }
+    }
+}
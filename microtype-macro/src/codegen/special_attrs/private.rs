@@ -0,0 +1,51 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{spanned::Spanned, Attribute};
+
+fn duplicate_private(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `private` attribute found"))
+}
+
+pub fn strip_private_attr(attrs: Vec<Attribute>) -> Result<(Vec<Attribute>, bool), TokenStream> {
+    let (private, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("private"));
+
+    let private = match &private[..] {
+        [] => false,
+        [_single] => true,
+        [_, second, ..] => return Err(duplicate_private(second.span())),
+    };
+
+    Ok((attrs, private))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::{model::flatten, parse::MicrotypeMacro};
+
+    use super::*;
+
+    #[test]
+    fn strips_private_attr() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[derive(Foo)] #[private] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (attrs, private) = strip_private_attr(attrs).unwrap();
+
+        assert_eq!(attrs.len(), 1);
+        assert!(private);
+    }
+
+    #[test]
+    fn fails_on_duplicate_private() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[private] String { #[private] Email }").unwrap();
+        let microtypes = flatten(microtype);
+
+        strip_private_attr(microtypes[0].attrs.clone()).unwrap_err();
+    }
+}
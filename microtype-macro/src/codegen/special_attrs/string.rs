@@ -1,47 +1,241 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse_str, Ident, Type};
+use syn::{parse_str, Generics, Ident, Type};
 
-use super::helpers::fmt_impl;
+use super::{helpers::fmt_impl, CaseFolding, StringAttr};
+
+/// `String` isn't in the prelude of `#![no_std]` crates
+fn string_path() -> TokenStream {
+    quote! { ::microtype::__private::alloc::string::String }
+}
+
+/// `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash`, which all compare the case-folded characters
+///
+/// Unicode folding maps each character to uppercase and then lowercase, which (unlike just
+/// lowercasing) matches e.g. `"ß"` with `"SS"`. This is close to full Unicode case folding, but
+/// doesn't need any tables beyond what `core` already has
+fn case_insensitive_impls(name: &Ident, generics: &Generics, string: &StringAttr) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fold = |value| match string.case_insensitive {
+        None => None,
+        Some(CaseFolding::Ascii) => Some(quote! {
+            #value.0.bytes().map(|b| b.to_ascii_lowercase())
+        }),
+        Some(CaseFolding::Unicode) => Some(quote! {
+            #value.0
+                .chars()
+                .flat_map(::core::primitive::char::to_uppercase)
+                .flat_map(::core::primitive::char::to_lowercase)
+        }),
+    };
+
+    let (lhs, rhs) = match (fold(quote!(self)), fold(quote!(other))) {
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+        _ => return quote! {},
+    };
+
+    quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                ::core::iter::Iterator::eq(#lhs, #rhs)
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::iter::Iterator::cmp(#lhs, #rhs)
+            }
+        }
+
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                for c in #lhs {
+                    ::core::hash::Hash::hash(&c, state);
+                }
+                // like `str`, so that e.g. `("ab", "c")` and `("a", "bc")` hash differently
+                ::core::hash::Hasher::write_u8(state, 0xff);
+            }
+        }
+    }
+}
+
+/// Statements for `ValidatedMicrotype::try_new` that check the `#[string(...)]` constraints
+///
+/// The pattern is compiled the first time it's needed. Since it was already parsed by the macro,
+/// compiling it can only fail if it's too big
+pub fn string_constraints_check(name: &Ident, string: &StringAttr) -> TokenStream {
+    let microtype = name.to_string();
+    let fail = |constraint| {
+        quote! {
+            return Err(::microtype::StringConstraintError {
+                microtype: #microtype,
+                constraint: ::microtype::StringConstraint::#constraint,
+            });
+        }
+    };
+    let mut check = TokenStream::new();
+
+    if string.ascii {
+        let fail = fail(quote!(Ascii));
+        check.extend(quote! {
+            if !inner.is_ascii() {
+                #fail
+            }
+        });
+    }
+
+    if string.min_len.is_some() || string.max_len.is_some() {
+        check.extend(quote! {
+            let len = inner.chars().count();
+        });
+    }
+    if let Some(min) = string.min_len {
+        let fail = fail(quote!(MinLen(#min)));
+        check.extend(quote! {
+            if len < #min {
+                #fail
+            }
+        });
+    }
+    if let Some(max) = string.max_len {
+        let fail = fail(quote!(MaxLen(#max)));
+        check.extend(quote! {
+            if len > #max {
+                #fail
+            }
+        });
+    }
+
+    if let Some(pattern) = &string.pattern {
+        let fail = fail(quote!(Pattern(#pattern)));
+        check.extend(quote! {
+            static PATTERN: ::microtype::__private::OnceLock<::microtype::__private::regex::Regex> =
+                ::microtype::__private::OnceLock::new();
+            let pattern = PATTERN.get_or_init(|| {
+                // anchored, so the whole value has to match rather than just part of it
+                ::microtype::__private::regex::Regex::new(::core::concat!("^(?:", #pattern, ")$"))
+                    .expect("invalid pattern")
+            });
+            if !pattern.is_match(&inner) {
+                #fail
+            }
+        });
+    }
+
+    check
+}
+
+pub fn secret_string_impls(name: &Ident, generics: &Generics) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let string = string_path();
 
-pub fn secret_string_impls(name: &Ident) -> TokenStream {
     quote! {
-        impl ::core::str::FromStr for #name {
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
             type Err = ::core::convert::Infallible;
 
             fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
-                Ok(<Self as ::microtype::SecretMicrotype>::new(s.to_string()))
+                Ok(<Self as ::microtype::SecretMicrotype>::new(#string::from(s)))
             }
         }
     }
 }
 
-pub fn string_impls(name: &Ident, inner: &Type) -> TokenStream {
-        let display = fmt_impl(name, inner, &parse_str("::core::fmt::Display").unwrap());
+pub fn string_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    string_attr: &StringAttr,
+) -> TokenStream {
+    let display = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Display").unwrap(),
+    );
+    let case_insensitive_impls = case_insensitive_impls(name, generics, string_attr);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let string = string_path();
 
     quote! {
 
         #display
 
-        impl ::core::str::FromStr for #name {
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
             type Err = ::core::convert::Infallible;
 
             fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
-                Ok(Self(s.to_string()))
+                Ok(<Self as ::microtype::Microtype>::new(#string::from(s)))
             }
 
         }
 
-        impl ::std::convert::From<&::core::primitive::str> for #name {
+        impl #impl_generics ::core::convert::From<&::core::primitive::str> for #name #ty_generics #where_clause {
             fn from(s: &::core::primitive::str) -> Self {
-                Self::from(s.to_string())
+                Self::from(#string::from(s))
+            }
+        }
+
+        impl #impl_generics ::core::convert::AsRef<::core::primitive::str> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &::core::primitive::str {
+                &self.0
+            }
+        }
+
+        #case_insensitive_impls
+    }
+}
+
+pub fn validated_string_impls(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    error: &Type,
+    string_attr: &StringAttr,
+) -> TokenStream {
+    let display = fmt_impl(
+        name,
+        generics,
+        inner,
+        &parse_str("::core::fmt::Display").unwrap(),
+    );
+    let case_insensitive_impls = case_insensitive_impls(name, generics, string_attr);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let string = string_path();
+
+    quote! {
+
+        #display
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = #error;
+
+            fn from_str(s: &::core::primitive::str) -> Result<Self, Self::Err> {
+                <Self as ::microtype::ValidatedMicrotype>::try_new(#string::from(s))
             }
         }
 
-        impl ::core::convert::AsRef<::core::primitive::str> for #name {
+        impl #impl_generics ::core::convert::TryFrom<&::core::primitive::str> for #name #ty_generics #where_clause {
+            type Error = #error;
+
+            fn try_from(s: &::core::primitive::str) -> Result<Self, Self::Error> {
+                <Self as ::microtype::ValidatedMicrotype>::try_new(#string::from(s))
+            }
+        }
+
+        impl #impl_generics ::core::convert::AsRef<::core::primitive::str> for #name #ty_generics #where_clause {
             fn as_ref(&self) -> &::core::primitive::str {
                 &self.0
             }
         }
+
+        #case_insensitive_impls
     }
 }
@@ -0,0 +1,66 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{spanned::Spanned, Attribute, Path};
+
+fn duplicate_tagged(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `tagged` attribute found"))
+}
+
+fn generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[tagged]`"))
+}
+
+/// Returns the path of the `#[tagged]` attribute (for error spans), if present
+pub fn strip_tagged_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<Path>), TokenStream> {
+    let (tagged, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("tagged"));
+
+    let tagged = match &tagged[..] {
+        [] => None,
+        [single] if single.tokens.is_empty() => Some(single.path.clone()),
+        [single] => return Err(generic_err(single.tokens.span())),
+        [_, second, ..] => return Err(duplicate_tagged(second.span())),
+    };
+
+    Ok((attrs, tagged))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use crate::{model::flatten, parse::MicrotypeMacro};
+
+    use super::*;
+
+    #[test]
+    fn strips_tagged_attr() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[derive(Foo)] #[tagged] i64 { Id<T> }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (attrs, tagged) = strip_tagged_attr(attrs).unwrap();
+
+        assert_eq!(attrs.len(), 1);
+        assert!(tagged.is_some());
+    }
+
+    #[test]
+    fn fails_on_duplicate_tagged() {
+        let microtype: MicrotypeMacro = parse_str("#[tagged] i64 { #[tagged] Id<T> }").unwrap();
+        let microtypes = flatten(microtype);
+
+        strip_tagged_attr(microtypes[0].attrs.clone()).unwrap_err();
+    }
+
+    #[test]
+    fn fails_on_arguments() {
+        let microtype: MicrotypeMacro = parse_str("#[tagged(foo)] i64 { Id<T> }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        strip_tagged_attr(attrs).unwrap_err();
+    }
+}
@@ -1,6 +1,14 @@
-use proc_macro2::{Span, TokenStream};
-use quote::quote_spanned;
-use syn::{spanned::Spanned, Attribute};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Ident, Lit, LitByteStr, LitInt, LitStr, Token, Type,
+};
+
+use super::helpers::is_one_of;
 
 fn duplicate_string(span: Span) -> TokenStream {
     quote_spanned!(span => compile_error!("duplicate `string` attribute found"))
@@ -10,14 +18,579 @@ fn duplicate_int(span: Span) -> TokenStream {
     quote_spanned!(span => compile_error!("duplicate `int` attribute found"))
 }
 
+fn duplicate_float(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `float` attribute found"))
+}
+
 fn multiple_special_attrs() -> TokenStream {
-    quote::quote! { compile_error!("only one of `#[int]`, `#[string]` allowed") }
+    quote! { compile_error!("only one of `#[int]`, `#[float]`, `#[string]` allowed") }
+}
+
+fn int_generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[int(key, key = value, ...)]`, where `key = value` is one of `scalar_ops`, `ref_ops`, `neg`, `bit_ops`, `iter` (each with an optional bool), `overflow = \"checked\" | \"saturating\" | \"wrapping\"`, `range = a..=b` or `public_encoding = \"sqids\"` (with optional `alphabet = \"...\"` and `min_length = n`)"))
+}
+
+fn unknown_overflow(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected one of `\"checked\"`, `\"saturating\"`, `\"wrapping\"`"))
+}
+
+fn unknown_public_encoding(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `\"sqids\"`"))
+}
+
+fn invalid_alphabet(span: Span, reason: &str) -> TokenStream {
+    let message = format!("the alphabet must {reason}");
+    quote_spanned!(span => compile_error!(#message))
+}
+
+fn encoding_options_without_encoding(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("`alphabet` and `min_length` need `public_encoding = \"sqids\"`"))
+}
+
+fn float_generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[float(key, key = bool, ...)]`, where `key` is one of `scalar_ops`, `ref_ops`, `iter`, `finite`, `total_ord` or `hash`"))
+}
+
+fn hash_without_total_ord(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("`#[float(hash)]` requires `total_ord`, since `Hash` must agree with `Eq`"))
+}
+
+fn string_generic_err(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("expected `#[string(option, ...)]`, where `option` is one of `case_insensitive [= \"ascii\" | \"unicode\"]`, `normalize = [trim | lowercase | nfc, ...]`, `pattern = \"regex\"`, `min_len = n`, `max_len = n` or `ascii`"))
+}
+
+fn min_len_greater_than_max_len(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("`min_len` must not be greater than `max_len`"))
+}
+
+#[cfg(feature = "regex")]
+fn invalid_pattern(span: Span, error: regex_syntax::Error) -> TokenStream {
+    let message = format!("invalid pattern: {error}");
+    quote_spanned!(span => compile_error!(#message))
+}
+
+fn duplicate_key(span: Span, attr: &str) -> TokenStream {
+    let message = format!("duplicate key in `{attr}` attribute");
+    quote_spanned!(span => compile_error!(#message))
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug)]
 pub enum TypeAnnotation {
-    String,
-    Int,
+    String(StringAttr),
+    Int(IntAttr),
+    Float(FloatAttr),
+}
+
+/// Options for `#[string(...)]`, with the same syntax as [`IntAttr`]
+#[derive(Debug, Default)]
+pub struct StringAttr {
+    /// `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` ignore case
+    pub case_insensitive: Option<CaseFolding>,
+    /// Applied in order whenever a value is created
+    pub normalize: Vec<Normalization>,
+    /// A regex that the whole value must match
+    pub pattern: Option<LitStr>,
+    /// The minimum length, in `char`s
+    pub min_len: Option<usize>,
+    /// The maximum length, in `char`s
+    pub max_len: Option<usize>,
+    /// Only ASCII characters are allowed
+    pub ascii: bool,
+    /// The span of the options, if there were any
+    pub options: Option<Span>,
+}
+
+impl StringAttr {
+    /// Constraints make the microtype validated, with `StringConstraintError` as the error type
+    pub fn has_constraints(&self) -> bool {
+        self.pattern.is_some() || self.min_len.is_some() || self.max_len.is_some() || self.ascii
+    }
+}
+
+/// How `#[string(case_insensitive)]` compares characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseFolding {
+    /// Only ASCII letters are folded, so other characters must match exactly
+    Ascii,
+    /// Characters are folded using the Unicode case mappings
+    Unicode,
+}
+
+/// A step in `#[string(normalize = [...])]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    Trim,
+    Lowercase,
+    /// Unicode Normalization Form C, which needs the `nfc` feature
+    Nfc,
+}
+
+/// Options for `#[int(...)]`, which control which arithmetic impls are generated
+///
+/// A bare key (e.g. `#[int(bit_ops)]`) is the same as `key = true`
+#[derive(Debug)]
+pub struct IntAttr {
+    /// `T op Inner` and `T op= Inner`
+    pub scalar_ops: bool,
+    /// `T op &T`, `&T op &T` and `T op= &T`
+    pub ref_ops: bool,
+    /// `None` means only signed primitive inner types get `Neg`
+    pub neg: Option<bool>,
+    /// `BitAnd`, `BitOr`, `BitXor`, `Not`, `Shl` and `Shr`
+    pub bit_ops: bool,
+    /// `Sum` and `Product`
+    pub iter: bool,
+    /// How arithmetic operators handle overflow, `None` means the same as the inner type
+    pub overflow: Option<Overflow>,
+    /// Values outside this range are rejected, which makes the microtype validated
+    pub range: Option<IntRange>,
+    /// `Display`, `FromStr` and serde use this encoding, rather than the number
+    pub public_encoding: Option<PublicEncoding>,
+    /// The span of the options, if there were any
+    pub options: Option<Span>,
+}
+
+impl Default for IntAttr {
+    fn default() -> Self {
+        Self {
+            scalar_ops: true,
+            ref_ops: true,
+            neg: None,
+            bit_ops: false,
+            iter: true,
+            overflow: None,
+            range: None,
+            public_encoding: None,
+            options: None,
+        }
+    }
+}
+
+/// The `overflow` mode for `#[int(overflow = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Operators return `Option<Self>`
+    Checked,
+    Saturating,
+    Wrapping,
+}
+
+impl Overflow {
+    /// The prefix of the inherent methods on integer primitives, e.g. `checked_add`
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Checked => "checked",
+            Self::Saturating => "saturating",
+            Self::Wrapping => "wrapping",
+        }
+    }
+}
+
+/// The [Sqids](https://sqids.org) alphabet that is used when none is given
+const DEFAULT_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// `#[int(public_encoding = "sqids", alphabet = "...", min_length = n)]`
+#[derive(Debug)]
+pub struct PublicEncoding {
+    /// The alphabet before it is shuffled, which has already been checked
+    pub alphabet: LitByteStr,
+    /// Shorter IDs are padded to this length
+    pub min_length: u8,
+    /// The span of `public_encoding`
+    pub span: Span,
+}
+
+impl PublicEncoding {
+    /// Sqids encodes `u64`s, so other inner types would be lossy
+    pub fn supports_inner(&self, inner: &Type) -> bool {
+        is_one_of(inner, &["u8", "u16", "u32", "u64", "usize"])
+    }
+}
+
+/// Sqids needs at least 3 unique, single byte characters
+fn check_alphabet(alphabet: &LitStr) -> Result<LitByteStr, TokenStream> {
+    let value = alphabet.value();
+    let span = alphabet.span();
+
+    if !value.is_ascii() {
+        return Err(invalid_alphabet(span, "only contain ASCII characters"));
+    }
+    if value.len() < 3 {
+        return Err(invalid_alphabet(span, "have at least 3 characters"));
+    }
+    if value
+        .bytes()
+        .enumerate()
+        .any(|(i, byte)| value.bytes().skip(i + 1).any(|other| other == byte))
+    {
+        return Err(invalid_alphabet(span, "not contain duplicate characters"));
+    }
+
+    Ok(LitByteStr::new(value.as_bytes(), span))
+}
+
+/// Options for `#[float(...)]`, with the same syntax as [`IntAttr`]
+#[derive(Debug)]
+pub struct FloatAttr {
+    /// `T op Inner` and `T op= Inner`
+    pub scalar_ops: bool,
+    /// `T op &T`, `&T op &T` and `T op= &T`
+    pub ref_ops: bool,
+    /// `Sum` and `Product`
+    pub iter: bool,
+    /// NaN and infinity are rejected, which makes the microtype validated
+    pub finite: bool,
+    /// `PartialEq`, `Eq`, `PartialOrd` and `Ord`, using `total_cmp`
+    pub total_ord: bool,
+    /// `Hash`, using the bits of the value, which is consistent with `total_cmp`
+    pub hash: bool,
+    /// The span of the options, if there were any
+    pub options: Option<Span>,
+}
+
+impl Default for FloatAttr {
+    fn default() -> Self {
+        Self {
+            scalar_ops: true,
+            ref_ops: true,
+            iter: true,
+            finite: false,
+            total_ord: false,
+            hash: false,
+            options: None,
+        }
+    }
+}
+
+/// A single `key` or `key = value` in `#[int(...)]` or `#[float(...)]`
+struct TypeOption {
+    key: Ident,
+    value: Option<TypeOptionValue>,
+}
+
+enum TypeOptionValue {
+    Lit(Lit),
+    Range(IntRange),
+    List(Punctuated<Ident, Token![,]>),
+}
+
+impl Parse for TypeOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if !input.peek(Token![=]) {
+            return Ok(Self { key, value: None });
+        }
+
+        let _: Token![=] = input.parse()?;
+        let value = match key.to_string().as_str() {
+            "range" => TypeOptionValue::Range(input.parse()?),
+            "normalize" => {
+                let content;
+                bracketed!(content in input);
+                TypeOptionValue::List(content.parse_terminated(Ident::parse)?)
+            }
+            _ => TypeOptionValue::Lit(input.parse()?),
+        };
+
+        Ok(Self {
+            key,
+            value: Some(value),
+        })
+    }
+}
+
+/// The bounds of `#[int(range = ...)]`, which can be any of `a..b`, `a..=b`, `a..` or `..=b`
+///
+/// The bounds are kept as tokens, so they can be any const expression of the inner type
+#[derive(Debug)]
+pub struct IntRange {
+    pub start: Option<TokenStream>,
+    pub end: Option<TokenStream>,
+    pub inclusive: bool,
+}
+
+impl IntRange {
+    /// The smallest allowed value
+    pub fn min(&self, inner: &Type) -> TokenStream {
+        match &self.start {
+            Some(start) => quote!(#start),
+            None => quote!(<#inner>::MIN),
+        }
+    }
+
+    /// The largest allowed value
+    ///
+    /// An exclusive end of `MIN` would overflow, so that's a compile error instead
+    pub fn max(&self, inner: &Type) -> TokenStream {
+        match (&self.end, self.inclusive) {
+            (Some(end), true) => quote!(#end),
+            (Some(end), false) => quote_spanned! {
+                end.span() => match <#inner>::checked_sub(#end, 1) {
+                    ::core::option::Option::Some(max) => max,
+                    ::core::option::Option::None => ::core::panic!("the range is empty"),
+                }
+            },
+            (None, _) => quote!(<#inner>::MAX),
+        }
+    }
+}
+
+impl Parse for IntRange {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut start = TokenStream::new();
+        while !input.peek(Token![..]) {
+            if input.is_empty() || input.peek(Token![,]) {
+                return Err(input.error("expected a range, e.g. `1..=100`"));
+            }
+            start.extend([input.parse::<TokenTree>()?]);
+        }
+
+        let inclusive = match input.peek(Token![..=]) {
+            true => input.parse::<Token![..=]>().map(|_| true)?,
+            false => input.parse::<Token![..]>().map(|_| false)?,
+        };
+
+        let mut end = TokenStream::new();
+        while !input.is_empty() && !input.peek(Token![,]) {
+            end.extend([input.parse::<TokenTree>()?]);
+        }
+
+        if inclusive && end.is_empty() {
+            return Err(input.error("inclusive ranges need an end, e.g. `1..=100`"));
+        }
+
+        Ok(Self {
+            start: (!start.is_empty()).then_some(start),
+            end: (!end.is_empty()).then_some(end),
+            inclusive,
+        })
+    }
+}
+
+fn parse_int_attr(attr: &Attribute) -> Result<IntAttr, TokenStream> {
+    // it's just `#[int]`
+    if attr.tokens.is_empty() {
+        return Ok(IntAttr::default());
+    }
+
+    let options = match attr.parse_args_with(Punctuated::<TypeOption, Token![,]>::parse_terminated)
+    {
+        Ok(options) => options,
+        Err(e) => return Err(int_generic_err(e.span())),
+    };
+
+    let mut result = IntAttr {
+        options: options.first().map(|option| option.key.span()),
+        ..IntAttr::default()
+    };
+    let mut seen = vec![];
+    let mut public_encoding = None;
+    let mut alphabet = None;
+    let mut min_length = None;
+
+    for TypeOption { key, value } in options {
+        let flag = match (key.to_string().as_str(), value) {
+            ("overflow", Some(TypeOptionValue::Lit(Lit::Str(mode)))) => {
+                result.overflow = Some(match mode.value().as_str() {
+                    "checked" => Overflow::Checked,
+                    "saturating" => Overflow::Saturating,
+                    "wrapping" => Overflow::Wrapping,
+                    _ => return Err(unknown_overflow(mode.span())),
+                });
+                None
+            }
+            ("range", Some(TypeOptionValue::Range(range))) => {
+                result.range = Some(range);
+                None
+            }
+            ("public_encoding", Some(TypeOptionValue::Lit(Lit::Str(encoding)))) => {
+                if encoding.value() != "sqids" {
+                    return Err(unknown_public_encoding(encoding.span()));
+                }
+                public_encoding = Some(key.span());
+                None
+            }
+            ("alphabet", Some(TypeOptionValue::Lit(Lit::Str(lit)))) => {
+                alphabet = Some(check_alphabet(&lit)?);
+                None
+            }
+            ("min_length", Some(TypeOptionValue::Lit(Lit::Int(lit)))) => {
+                // the same limit as other Sqids implementations
+                min_length = Some(
+                    lit.base10_parse()
+                        .map_err(|_| int_generic_err(lit.span()))?,
+                );
+                None
+            }
+            ("overflow" | "range" | "public_encoding" | "alphabet" | "min_length", _) => {
+                return Err(int_generic_err(key.span()))
+            }
+            (_, None) => Some(true),
+            (_, Some(TypeOptionValue::Lit(Lit::Bool(b)))) => Some(b.value),
+            (_, Some(TypeOptionValue::Lit(other))) => return Err(int_generic_err(other.span())),
+            (_, Some(TypeOptionValue::Range(_) | TypeOptionValue::List(_))) => {
+                return Err(int_generic_err(key.span()))
+            }
+        };
+
+        match (key.to_string().as_str(), flag) {
+            (_, None) => {}
+            ("scalar_ops", Some(value)) => result.scalar_ops = value,
+            ("ref_ops", Some(value)) => result.ref_ops = value,
+            ("neg", Some(value)) => result.neg = Some(value),
+            ("bit_ops", Some(value)) => result.bit_ops = value,
+            ("iter", Some(value)) => result.iter = value,
+            _ => return Err(int_generic_err(key.span())),
+        }
+
+        if seen.contains(&key) {
+            return Err(duplicate_key(key.span(), "int"));
+        }
+        seen.push(key);
+    }
+
+    result.public_encoding = match (public_encoding, alphabet, min_length) {
+        (Some(span), alphabet, min_length) => Some(PublicEncoding {
+            alphabet: alphabet
+                .unwrap_or_else(|| LitByteStr::new(DEFAULT_ALPHABET.as_bytes(), span)),
+            min_length: min_length.unwrap_or(0),
+            span,
+        }),
+        (None, None, None) => None,
+        (None, _, _) => return Err(encoding_options_without_encoding(result.options.unwrap())),
+    };
+
+    Ok(result)
+}
+
+fn parse_string_attr(attr: &Attribute) -> Result<StringAttr, TokenStream> {
+    // it's just `#[string]`
+    if attr.tokens.is_empty() {
+        return Ok(StringAttr::default());
+    }
+
+    let options = match attr.parse_args_with(Punctuated::<TypeOption, Token![,]>::parse_terminated)
+    {
+        Ok(options) => options,
+        Err(e) => return Err(string_generic_err(e.span())),
+    };
+
+    let mut result = StringAttr {
+        options: options.first().map(|option| option.key.span()),
+        ..StringAttr::default()
+    };
+    let mut seen = vec![];
+
+    for TypeOption { key, value } in options {
+        match (key.to_string().as_str(), value) {
+            ("case_insensitive", None) => result.case_insensitive = Some(CaseFolding::Ascii),
+            ("case_insensitive", Some(TypeOptionValue::Lit(Lit::Str(folding)))) => {
+                result.case_insensitive = Some(match folding.value().as_str() {
+                    "ascii" => CaseFolding::Ascii,
+                    "unicode" => CaseFolding::Unicode,
+                    _ => return Err(string_generic_err(folding.span())),
+                });
+            }
+            ("normalize", Some(TypeOptionValue::List(steps))) => {
+                for step in steps {
+                    result.normalize.push(match step.to_string().as_str() {
+                        "trim" => Normalization::Trim,
+                        "lowercase" => Normalization::Lowercase,
+                        "nfc" => Normalization::Nfc,
+                        _ => return Err(string_generic_err(step.span())),
+                    });
+                }
+            }
+            ("pattern", Some(TypeOptionValue::Lit(Lit::Str(pattern)))) => {
+                #[cfg(feature = "regex")]
+                if let Err(e) = regex_syntax::Parser::new().parse(&pattern.value()) {
+                    return Err(invalid_pattern(pattern.span(), e));
+                }
+                result.pattern = Some(pattern);
+            }
+            ("min_len", Some(TypeOptionValue::Lit(Lit::Int(len)))) => {
+                result.min_len = Some(parse_len(&len)?);
+            }
+            ("max_len", Some(TypeOptionValue::Lit(Lit::Int(len)))) => {
+                result.max_len = Some(parse_len(&len)?);
+            }
+            ("ascii", None) => result.ascii = true,
+            _ => return Err(string_generic_err(key.span())),
+        }
+
+        if seen.contains(&key) {
+            return Err(duplicate_key(key.span(), "string"));
+        }
+        seen.push(key);
+    }
+
+    if let (Some(min), Some(max)) = (result.min_len, result.max_len) {
+        if min > max {
+            return Err(min_len_greater_than_max_len(result.options.unwrap()));
+        }
+    }
+
+    Ok(result)
+}
+
+fn parse_len(len: &LitInt) -> Result<usize, TokenStream> {
+    len.base10_parse()
+        .map_err(|_| string_generic_err(len.span()))
+}
+
+fn parse_float_attr(attr: &Attribute) -> Result<FloatAttr, TokenStream> {
+    // it's just `#[float]`
+    if attr.tokens.is_empty() {
+        return Ok(FloatAttr::default());
+    }
+
+    let options = match attr.parse_args_with(Punctuated::<TypeOption, Token![,]>::parse_terminated)
+    {
+        Ok(options) => options,
+        Err(e) => return Err(float_generic_err(e.span())),
+    };
+
+    let mut result = FloatAttr {
+        options: options.first().map(|option| option.key.span()),
+        ..FloatAttr::default()
+    };
+    let mut seen = vec![];
+
+    for TypeOption { key, value } in options {
+        let value = match value {
+            None => true,
+            Some(TypeOptionValue::Lit(Lit::Bool(b))) => b.value,
+            Some(TypeOptionValue::Lit(other)) => return Err(float_generic_err(other.span())),
+            Some(TypeOptionValue::Range(_) | TypeOptionValue::List(_)) => {
+                return Err(float_generic_err(key.span()))
+            }
+        };
+
+        match key.to_string().as_str() {
+            "scalar_ops" => result.scalar_ops = value,
+            "ref_ops" => result.ref_ops = value,
+            "iter" => result.iter = value,
+            "finite" => result.finite = value,
+            "total_ord" => result.total_ord = value,
+            "hash" => result.hash = value,
+            _ => return Err(float_generic_err(key.span())),
+        }
+
+        if seen.contains(&key) {
+            return Err(duplicate_key(key.span(), "float"));
+        }
+        seen.push(key);
+    }
+
+    if let (true, false, Some(hash)) = (
+        result.hash,
+        result.total_ord,
+        seen.iter().find(|key| *key == "hash"),
+    ) {
+        return Err(hash_without_total_ord(hash.span()));
+    }
+
+    Ok(result)
 }
 
 pub fn strip_type_annotation(
@@ -28,8 +601,8 @@ pub fn strip_type_annotation(
         .partition(|attr| attr.path.is_ident("string"));
 
     let string = match &string[..] {
-        [] => false,
-        [_single] => true,
+        [] => None,
+        [single] => Some(parse_string_attr(single)?),
         [_, second, ..] => return Err(duplicate_string(second.span())),
     };
 
@@ -38,15 +611,26 @@ pub fn strip_type_annotation(
         .partition(|attr| attr.path.is_ident("int"));
 
     let int = match &int[..] {
-        [] => false,
-        [_single] => true,
+        [] => None,
+        [single] => Some(parse_int_attr(single)?),
         [_, second, ..] => return Err(duplicate_int(second.span())),
     };
 
-    let type_annotations = match (string, int) {
-        (false, false) => None,
-        (true, false) => Some(TypeAnnotation::String),
-        (false, true) => Some(TypeAnnotation::Int),
+    let (float, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("float"));
+
+    let float = match &float[..] {
+        [] => None,
+        [single] => Some(parse_float_attr(single)?),
+        [_, second, ..] => return Err(duplicate_float(second.span())),
+    };
+
+    let type_annotations = match (string, int, float) {
+        (None, None, None) => None,
+        (Some(string), None, None) => Some(TypeAnnotation::String(string)),
+        (None, Some(int), None) => Some(TypeAnnotation::Int(int)),
+        (None, None, Some(float)) => Some(TypeAnnotation::Float(float)),
         _ => return Err(multiple_special_attrs()),
     };
 
@@ -55,7 +639,7 @@ pub fn strip_type_annotation(
 
 #[cfg(test)]
 mod tests {
-    use syn::parse_str;
+    use syn::{parse_quote, parse_str};
 
     use crate::parse::MicrotypeMacro;
 
@@ -70,7 +654,255 @@ mod tests {
         let (attrs, type_annotations) = strip_type_annotation(attrs).unwrap();
 
         assert_eq!(attrs.len(), 1);
-        assert_eq!(type_annotations, Some(TypeAnnotation::String));
+        assert!(matches!(
+            type_annotations,
+            Some(TypeAnnotation::String(StringAttr {
+                case_insensitive: None,
+                options: None,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn parses_string_options() {
+        let case_insensitive = |s: &str| {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[string({s})] String {{ Email }}")).unwrap();
+            match strip_type_annotation(microtype.0[0].attrs.clone()) {
+                Ok((_, Some(TypeAnnotation::String(string)))) => string.case_insensitive,
+                other => panic!("expected string, got {other:?}"),
+            }
+        };
+
+        assert_eq!(
+            case_insensitive("case_insensitive"),
+            Some(CaseFolding::Ascii)
+        );
+        assert_eq!(
+            case_insensitive(r#"case_insensitive = "ascii""#),
+            Some(CaseFolding::Ascii)
+        );
+        assert_eq!(
+            case_insensitive(r#"case_insensitive = "unicode""#),
+            Some(CaseFolding::Unicode)
+        );
+
+        let microtype: MicrotypeMacro =
+            parse_str("#[string(normalize = [trim, lowercase, nfc])] String { Email }").unwrap();
+        match strip_type_annotation(microtype.0[0].attrs.clone()) {
+            Ok((_, Some(TypeAnnotation::String(string)))) => assert_eq!(
+                string.normalize,
+                [
+                    Normalization::Trim,
+                    Normalization::Lowercase,
+                    Normalization::Nfc
+                ]
+            ),
+            other => panic!("expected string, got {other:?}"),
+        }
+
+        let microtype: MicrotypeMacro =
+            parse_str(r#"#[string(pattern = "^[a-z]+$", min_len = 3, max_len = 32, ascii)] String { Username }"#)
+                .unwrap();
+        match strip_type_annotation(microtype.0[0].attrs.clone()) {
+            Ok((_, Some(TypeAnnotation::String(string)))) => {
+                assert!(string.has_constraints());
+                assert_eq!(string.pattern.unwrap().value(), "^[a-z]+$");
+                assert_eq!(string.min_len, Some(3));
+                assert_eq!(string.max_len, Some(32));
+                assert!(string.ascii);
+            }
+            other => panic!("expected string, got {other:?}"),
+        }
+
+        for s in [
+            "normalize = [uppercase]",
+            "normalize = trim",
+            "normalize",
+            r#"case_insensitive = "turkish""#,
+            "case_insensitive = true",
+            "case_insensitive, case_insensitive",
+            "min_len = -1",
+            "min_len = 3, max_len = 2",
+            r#"max_len = "3""#,
+            "pattern = 3",
+            "ascii = true",
+            "foo",
+        ] {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[string({s})] String {{ Email }}")).unwrap();
+            strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn parses_int_options() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[int(bit_ops, scalar_ops = false, neg = true)] i32 { Count }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (_, type_annotations) = strip_type_annotation(attrs).unwrap();
+        let int = match type_annotations {
+            Some(TypeAnnotation::Int(int)) => int,
+            other => panic!("expected int, got {other:?}"),
+        };
+
+        assert!(int.bit_ops);
+        assert!(!int.scalar_ops);
+        assert_eq!(int.neg, Some(true));
+        assert!(int.ref_ops);
+        assert!(int.iter);
+        assert!(int.options.is_some());
+        assert_eq!(int.overflow, None);
+    }
+
+    #[test]
+    fn parses_overflow_mode() {
+        let microtype: MicrotypeMacro =
+            parse_str(r#"#[int(overflow = "saturating")] u64 { Count }"#).unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (_, type_annotations) = strip_type_annotation(attrs).unwrap();
+        let int = match type_annotations {
+            Some(TypeAnnotation::Int(int)) => int,
+            other => panic!("expected int, got {other:?}"),
+        };
+
+        assert_eq!(int.overflow, Some(Overflow::Saturating));
+
+        let microtype: MicrotypeMacro =
+            parse_str(r#"#[int(overflow = "panic")] u64 { Count }"#).unwrap();
+        strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+
+        let microtype: MicrotypeMacro = parse_str("#[int(overflow)] u64 { Count }").unwrap();
+        strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+    }
+
+    #[test]
+    fn fails_on_unknown_or_duplicate_int_option() {
+        let microtype: MicrotypeMacro = parse_str("#[int(foo)] i32 { Count }").unwrap();
+        strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+
+        let microtype: MicrotypeMacro =
+            parse_str("#[int(iter, iter = false)] i32 { Count }").unwrap();
+        strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+    }
+
+    #[test]
+    fn parses_public_encoding() {
+        let encoding = |s: &str| {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[int({s})] u64 {{ OrderId }}")).unwrap();
+            match strip_type_annotation(microtype.0[0].attrs.clone()) {
+                Ok((_, Some(TypeAnnotation::Int(int)))) => int.public_encoding,
+                other => panic!("expected int, got {other:?}"),
+            }
+        };
+
+        let default = encoding(r#"public_encoding = "sqids""#).unwrap();
+        assert_eq!(default.alphabet.value(), DEFAULT_ALPHABET.as_bytes());
+        assert_eq!(default.min_length, 0);
+
+        let custom =
+            encoding(r#"public_encoding = "sqids", alphabet = "xyz123", min_length = 8"#).unwrap();
+        assert_eq!(custom.alphabet.value(), b"xyz123");
+        assert_eq!(custom.min_length, 8);
+
+        assert!(encoding("bit_ops").is_none());
+
+        for s in [
+            r#"public_encoding = "hashids""#,
+            "public_encoding",
+            r#"alphabet = "abc""#,
+            "min_length = 3",
+            r#"public_encoding = "sqids", alphabet = "ab""#,
+            r#"public_encoding = "sqids", alphabet = "abca""#,
+            r#"public_encoding = "sqids", alphabet = "abcé""#,
+            r#"public_encoding = "sqids", min_length = 256"#,
+            r#"public_encoding = "sqids", public_encoding = "sqids""#,
+        ] {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[int({s})] u64 {{ OrderId }}")).unwrap();
+            strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let range = |s: &str| {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[int({s})] u8 {{ Percentage }}")).unwrap();
+            let attrs = microtype.0[0].attrs.clone();
+            match strip_type_annotation(attrs) {
+                Ok((_, Some(TypeAnnotation::Int(int)))) => int.range.unwrap(),
+                other => panic!("expected int, got {other:?}"),
+            }
+        };
+        let inner = parse_quote!(u8);
+
+        let inclusive = range("range = 1..=100, iter = false");
+        assert_eq!(inclusive.min(&inner).to_string(), "1");
+        assert_eq!(inclusive.max(&inner).to_string(), "100");
+
+        let exclusive = range("range = -5..LIMIT");
+        assert_eq!(exclusive.min(&inner).to_string(), "- 5");
+        assert!(exclusive
+            .max(&inner)
+            .to_string()
+            .contains("< u8 > :: checked_sub (LIMIT , 1)"));
+
+        let from = range("range = 10..");
+        assert_eq!(from.max(&inner).to_string(), "< u8 > :: MAX");
+
+        let to = range("range = ..=10");
+        assert_eq!(to.min(&inner).to_string(), "< u8 > :: MIN");
+    }
+
+    #[test]
+    fn fails_on_invalid_range() {
+        for s in ["range = 10", "range = 1..=", "range", r#"range = "1..10""#] {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[int({s})] u8 {{ Percentage }}")).unwrap();
+            strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn parses_float_options() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[float(finite, total_ord, hash, scalar_ops = false)] f64 { Latitude }")
+                .unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (_, type_annotations) = strip_type_annotation(attrs).unwrap();
+        let float = match type_annotations {
+            Some(TypeAnnotation::Float(float)) => float,
+            other => panic!("expected float, got {other:?}"),
+        };
+
+        assert!(float.finite);
+        assert!(float.total_ord);
+        assert!(float.hash);
+        assert!(!float.scalar_ops);
+        assert!(float.ref_ops);
+        assert!(float.iter);
+        assert!(float.options.is_some());
+    }
+
+    #[test]
+    fn fails_on_invalid_float_options() {
+        for s in [
+            "hash",
+            "bit_ops",
+            "finite, finite",
+            "range = 0..1",
+            r#"finite = "yes""#,
+        ] {
+            let microtype: MicrotypeMacro =
+                parse_str(&format!("#[float({s})] f64 {{ Latitude }}")).unwrap();
+            strip_type_annotation(microtype.0[0].attrs.clone()).unwrap_err();
+        }
     }
 
     #[test]
@@ -78,5 +910,9 @@ mod tests {
         let microtype: MicrotypeMacro = parse_str("#[int] #[string] String { Email }").unwrap();
         let attrs = microtype.0[0].attrs.clone();
         strip_type_annotation(attrs).unwrap_err();
+
+        let microtype: MicrotypeMacro = parse_str("#[int] #[float] f64 { Latitude }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+        strip_type_annotation(attrs).unwrap_err();
     }
 }
@@ -0,0 +1,115 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned,
+    Attribute, Ident, Path, Token, Type,
+};
+
+fn duplicate_validate(span: Span) -> TokenStream {
+    quote_spanned!(span => compile_error!("duplicate `validate` attribute found"))
+}
+
+/// The contents of a `#[validate(path::to::fn)]` or `#[validate(path::to::fn, error = Error)]`
+/// attribute
+#[derive(Debug)]
+pub struct ValidateAttr {
+    pub validator: Path,
+    pub error: Type,
+}
+
+impl Parse for ValidateAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let validator = input.parse()?;
+
+        if input.is_empty() {
+            return Ok(Self {
+                validator,
+                error: parse_quote!(&'static ::core::primitive::str),
+            });
+        }
+
+        let _: Token![,] = input.parse()?;
+        let key: Ident = input.parse()?;
+        if key != "error" {
+            return Err(syn::Error::new(key.span(), "expected `error = ...`"));
+        }
+        let _: Token![=] = input.parse()?;
+        let error = input.parse()?;
+
+        Ok(Self { validator, error })
+    }
+}
+
+pub fn strip_validate_attr(
+    attrs: Vec<Attribute>,
+) -> Result<(Vec<Attribute>, Option<ValidateAttr>), TokenStream> {
+    let (validate, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("validate"));
+
+    let validate = match &validate[..] {
+        [] => None,
+        [single] => match single.parse_args() {
+            Ok(validate) => Some(validate),
+            Err(e) => return Err(e.to_compile_error()),
+        },
+        [_, second, ..] => return Err(duplicate_validate(second.span())),
+    };
+
+    Ok((attrs, validate))
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+    use syn::parse_str;
+
+    use crate::parse::MicrotypeMacro;
+
+    use super::*;
+
+    #[test]
+    fn strips_validate_attr() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[derive(Foo)] #[validate(foo::not_empty)] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (attrs, validate) = strip_validate_attr(attrs).unwrap();
+        let validate = validate.unwrap();
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(
+            validate.validator.to_token_stream().to_string(),
+            "foo :: not_empty"
+        );
+        assert_eq!(
+            validate.error.to_token_stream().to_string(),
+            "& 'static :: core :: primitive :: str"
+        );
+    }
+
+    #[test]
+    fn parses_custom_error_type() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[validate(is_email, error = EmailError)] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        let (_, validate) = strip_validate_attr(attrs).unwrap();
+
+        assert_eq!(
+            validate.unwrap().error.to_token_stream().to_string(),
+            "EmailError"
+        );
+    }
+
+    #[test]
+    fn fails_on_unknown_key() {
+        let microtype: MicrotypeMacro =
+            parse_str("#[validate(is_email, foo = EmailError)] String { Email }").unwrap();
+        let attrs = microtype.0[0].attrs.clone();
+
+        strip_validate_attr(attrs).unwrap_err();
+    }
+}
@@ -0,0 +1,171 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, GenericParam, Generics, Ident, Type};
+
+use super::generics::with_predicates;
+
+/// The type parameter of a `#[tagged]` microtype that only appears in a `PhantomData` field
+///
+/// This is always the last type parameter, e.g. `T` in `Id<T>`
+pub struct Tag {
+    param: Ident,
+}
+
+impl Tag {
+    pub fn find(generics: &Generics) -> Option<Self> {
+        generics.params.iter().rev().find_map(|param| match param {
+            GenericParam::Type(ty) => Some(Self {
+                param: ty.ident.clone(),
+            }),
+            _ => None,
+        })
+    }
+
+    pub fn param(&self) -> &Ident {
+        &self.param
+    }
+
+    /// The extra field declaration, including the leading comma
+    ///
+    /// `fn() -> T` is used so that the microtype is `Send`, `Sync`, etc. regardless of the tag
+    pub fn field(&self, serde: bool) -> TokenStream {
+        let param = &self.param;
+        let serde_skip = match serde {
+            true => quote! { #[serde(skip)] },
+            false => quote! {},
+        };
+
+        quote! {
+            , #serde_skip ::core::marker::PhantomData<fn() -> #param>
+        }
+    }
+
+    /// The extra field value when constructing the microtype, including the leading comma
+    pub fn init(&self) -> TokenStream {
+        quote! {
+            , ::core::marker::PhantomData
+        }
+    }
+}
+
+/// Bounds only apply to `field_ty`, so unlike `#[derive(...)]`, the tag doesn't need to implement
+/// the trait
+///
+/// The bound uses a dummy `for<'__a>`, since otherwise a bound like `String: Copy` would be a
+/// compile error rather than making the impl unusable
+fn bounded(generics: &Generics, field_ty: &Type, bound: TokenStream) -> Generics {
+    with_predicates(generics, [parse_quote!(for<'__a> #field_ty: #bound)])
+}
+
+/// `Clone` impl for a tagged microtype, where `field_ty` is the type of the first field
+pub fn clone_impl(name: &Ident, generics: &Generics, field_ty: &Type, tag: &Tag) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let clone_generics = bounded(generics, field_ty, quote!(::core::clone::Clone));
+    let (impl_generics, _, where_clause) = clone_generics.split_for_impl();
+    let init = tag.init();
+
+    quote! {
+        impl #impl_generics ::core::clone::Clone for #name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self(::core::clone::Clone::clone(&self.0) #init)
+            }
+        }
+    }
+}
+
+/// `Debug` impl for a tagged microtype, which leaves out the tag
+pub fn debug_impl(name: &Ident, generics: &Generics, field_ty: &Type) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let debug_generics = bounded(generics, field_ty, quote!(::core::fmt::Debug));
+    let (impl_generics, _, where_clause) = debug_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(::core::stringify!(#name)).field(&self.0).finish()
+            }
+        }
+    }
+}
+
+/// `Copy`, comparison and `Hash` impls for a tagged microtype
+fn comparison_impls(name: &Ident, generics: &Generics, field_ty: &Type) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let copy_generics = bounded(generics, field_ty, quote!(::core::marker::Copy));
+    let (copy_impl_generics, _, copy_where_clause) = copy_generics.split_for_impl();
+    let eq_generics = bounded(generics, field_ty, quote!(::core::cmp::PartialEq));
+    let (eq_impl_generics, _, eq_where_clause) = eq_generics.split_for_impl();
+    let total_eq_generics = bounded(generics, field_ty, quote!(::core::cmp::Eq));
+    let (total_eq_impl_generics, _, total_eq_where_clause) = total_eq_generics.split_for_impl();
+    let ord_generics = bounded(generics, field_ty, quote!(::core::cmp::PartialOrd));
+    let (ord_impl_generics, _, ord_where_clause) = ord_generics.split_for_impl();
+    let total_ord_generics = bounded(generics, field_ty, quote!(::core::cmp::Ord));
+    let (total_ord_impl_generics, _, total_ord_where_clause) = total_ord_generics.split_for_impl();
+    let hash_generics = bounded(generics, field_ty, quote!(::core::hash::Hash));
+    let (hash_impl_generics, _, hash_where_clause) = hash_generics.split_for_impl();
+
+    quote! {
+        impl #copy_impl_generics ::core::marker::Copy for #name #ty_generics #copy_where_clause {}
+
+        impl #eq_impl_generics ::core::cmp::PartialEq for #name #ty_generics #eq_where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                ::core::cmp::PartialEq::eq(&self.0, &other.0)
+            }
+        }
+
+        impl #total_eq_impl_generics ::core::cmp::Eq for #name #ty_generics #total_eq_where_clause {}
+
+        impl #ord_impl_generics ::core::cmp::PartialOrd for #name #ty_generics #ord_where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::cmp::PartialOrd::partial_cmp(&self.0, &other.0)
+            }
+        }
+
+        impl #total_ord_impl_generics ::core::cmp::Ord for #name #ty_generics #total_ord_where_clause {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(&self.0, &other.0)
+            }
+        }
+
+        impl #hash_impl_generics ::core::hash::Hash for #name #ty_generics #hash_where_clause {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                ::core::hash::Hash::hash(&self.0, state)
+            }
+        }
+    }
+}
+
+/// All the impls that `#[derive(...)]` can't provide for a tagged microtype, since the derived
+/// impls would require the tag to implement the trait too
+pub fn tagged_impls(name: &Ident, generics: &Generics, inner: &Type, tag: &Tag) -> TokenStream {
+    let clone_impl = clone_impl(name, generics, inner, tag);
+    let debug_impl = debug_impl(name, generics, inner);
+    let comparison_impls = comparison_impls(name, generics, inner);
+
+    quote! {
+        #clone_impl
+        #debug_impl
+        #comparison_impls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_is_last_type_param() {
+        let generics: Generics = parse_quote!(<'a, T, U: Entity, const N: usize>);
+        let tag = Tag::find(&generics).unwrap();
+
+        assert_eq!(tag.param(), "U");
+    }
+
+    #[test]
+    fn no_tag_without_type_params() {
+        let generics: Generics = parse_quote!(<'a>);
+
+        assert!(Tag::find(&generics).is_none());
+    }
+}
@@ -0,0 +1,348 @@
+use super::{
+    diesel::diesel_impl_validated,
+    generics::{with_param, with_predicates},
+    special_attrs::{
+        checksum_check, checksum_impl, normalize_inner, one_of_check, one_of_impl,
+        prefixed_id_check, prefixed_id_impls, string_constraints_check, validated_float_impls,
+        validated_int_impls, validated_string_impls, FloatAttr, IntAttr, IntRange, SpecialAttrs,
+        StringAttr, TypeAnnotation, ValidateAttr,
+    },
+    tagged::{tagged_impls, Tag},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Attribute, Generics, Ident, LitStr, Type, Visibility};
+
+fn generate_struct(
+    name: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    inner: &Type,
+    tag: Option<&Tag>,
+    serde: bool,
+) -> TokenStream {
+    let where_clause = &generics.where_clause;
+    let tag_field = tag.map(|tag| tag.field(serde));
+
+    // the field is private, otherwise the validator could be bypassed
+    quote! {
+        #[repr(transparent)]
+        #vis struct #name #generics (#inner #tag_field) #where_clause;
+    }
+}
+
+fn generate_validated_microtype_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    check: &TokenStream,
+    error: &Type,
+    tag: Option<&Tag>,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let tag_init = tag.map(Tag::init);
+
+    quote! {
+        impl #impl_generics ::microtype::ValidatedMicrotype for #name #ty_generics #where_clause {
+            type Inner = #inner;
+            type Error = #error;
+
+            fn try_new(inner: Self::Inner) -> Result<Self, Self::Error> {
+                #check
+                Ok(Self(inner #tag_init))
+            }
+
+            fn into_inner(self) -> Self::Inner {
+                self.0
+            }
+
+            fn inner(&self) -> &Self::Inner {
+                &self.0
+            }
+
+            fn try_convert<__T: ::microtype::ValidatedMicrotype<Inner = Self::Inner>>(
+                self,
+            ) -> Result<__T, __T::Error> {
+                __T::try_new(self.0)
+            }
+        }
+    }
+}
+
+/// `MIN` and `MAX` constants, and a `const` constructor, for `#[int(range = ...)]` microtypes
+///
+/// The bounds can be any const expression, so an empty range can only be rejected at compile time
+/// by the generated code
+fn generate_range_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    range: &IntRange,
+    tag: Option<&Tag>,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let tag_init = tag.map(Tag::init);
+    let min = range.min(inner);
+    let max = range.max(inner);
+    let empty = LitStr::new(&format!("the range of `{name}` is empty"), name.span());
+    let empty_check = quote_spanned! {
+        name.span() => const _: () = ::core::assert!(#min <= #max, #empty);
+    };
+
+    quote! {
+        #empty_check
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The smallest allowed value
+            pub const MIN: #inner = #min;
+            /// The largest allowed value
+            pub const MAX: #inner = #max;
+
+            /// Create a value in a `const` context, panicking if it is out of range
+            ///
+            /// When used to initialize a `const` or `static`, an out-of-range value is a compile
+            /// error
+            pub const fn new_const(inner: #inner) -> Self {
+                if inner < Self::MIN || inner > Self::MAX {
+                    ::core::panic!("value is out of range");
+                }
+                Self(inner #tag_init)
+            }
+        }
+    }
+}
+
+fn generate_try_from_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    error: &Type,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::convert::TryFrom<#inner> for #name #ty_generics #where_clause {
+            type Error = #error;
+
+            fn try_from(inner: #inner) -> Result<Self, Self::Error> {
+                <Self as ::microtype::ValidatedMicrotype>::try_new(inner)
+            }
+        }
+    }
+}
+
+// `DerefMut` is deliberately missing, since it would allow the invariant to be broken
+fn generate_deref_impl(
+    name: &Ident,
+    generics: &Generics,
+    inner: &Type,
+    deref: bool,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if deref {
+        quote! {
+            impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+                type Target = #inner;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+fn serde_derives(serde: bool) -> TokenStream {
+    if serde {
+        quote! {
+            #[derive(::serde::Serialize)]
+            #[serde(transparent)]
+        }
+    } else {
+        quote! {}
+    }
+}
+
+// serde's `transparent` would bypass the validator, so this has to be written by hand
+fn deserialize_impl(name: &Ident, generics: &Generics, inner: &Type, serde: bool) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let de_generics = with_param(generics, parse_quote!('__de));
+    let de_generics = with_predicates(
+        &de_generics,
+        [parse_quote!(#inner: ::serde::Deserialize<'__de>)],
+    );
+    let (impl_generics, _, where_clause) = de_generics.split_for_impl();
+
+    if serde {
+        quote! {
+            impl #impl_generics ::serde::Deserialize<'__de> for #name #ty_generics #where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> Result<Self, __D::Error> {
+                    let inner = <#inner as ::serde::Deserialize<'__de>>::deserialize(deserializer)?;
+                    <Self as ::microtype::ValidatedMicrotype>::try_new(inner)
+                        .map_err(::serde::de::Error::custom)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+pub fn generate_validated(
+    inner: Type,
+    name: Ident,
+    generics: Generics,
+    vis: Visibility,
+    attrs: Vec<Attribute>,
+    special_attrs: SpecialAttrs,
+    hand_written: bool,
+) -> TokenStream {
+    let range = match &special_attrs.type_annotation {
+        Some(TypeAnnotation::Int(IntAttr {
+            range: Some(range), ..
+        })) => Some(range),
+        _ => None,
+    };
+    let int = matches!(special_attrs.type_annotation, Some(TypeAnnotation::Int(_)));
+    let finite = matches!(
+        special_attrs.type_annotation,
+        Some(TypeAnnotation::Float(FloatAttr { finite: true, .. }))
+    );
+
+    let (check, error): (TokenStream, Type) = match (&special_attrs.validate, range, finite) {
+        (Some(ValidateAttr { validator, error }), _, _) => {
+            (quote!(#validator(&inner)?;), error.clone())
+        }
+        (None, Some(_), _) => (
+            quote! {
+                if !(Self::MIN..=Self::MAX).contains(&inner) {
+                    return Err(::microtype::RangeError {
+                        value: inner,
+                        min: Self::MIN,
+                        max: Self::MAX,
+                    });
+                }
+            },
+            parse_quote!(::microtype::RangeError<#inner>),
+        ),
+        (None, None, true) => (
+            quote! {
+                if !<#inner>::is_finite(inner) {
+                    return Err(::microtype::NotFiniteError { value: inner });
+                }
+            },
+            parse_quote!(::microtype::NotFiniteError<#inner>),
+        ),
+        (None, None, false) => match (
+            &special_attrs.checksum,
+            &special_attrs.one_of,
+            &special_attrs.prefixed_id,
+            &special_attrs.type_annotation,
+        ) {
+            (Some(checksum), _, _, _) => (
+                checksum_check(&name, checksum, int),
+                parse_quote!(::microtype::ChecksumError),
+            ),
+            (None, Some(_), _, _) => (one_of_check(&name), parse_quote!(::microtype::OneOfError)),
+            (None, None, Some(prefixed_id), _) => (
+                prefixed_id_check(&name, prefixed_id),
+                parse_quote!(::microtype::PrefixError),
+            ),
+            (None, None, None, Some(TypeAnnotation::String(string))) if string.has_constraints() => (
+                string_constraints_check(&name, string),
+                parse_quote!(::microtype::StringConstraintError),
+            ),
+            _ => unreachable!(
+                "we are generating a validated type, so there must be a validator, range, `finite`, string constraint, `one_of`, `prefixed_id` or `checksum`"
+            ),
+        },
+    };
+
+    // values are normalized before they're validated
+    let normalize = normalize_inner(&special_attrs);
+    let check = quote! {
+        #normalize
+        #check
+    };
+
+    let overrides = &special_attrs.overrides;
+    let serde = overrides.serde();
+    let tag = special_attrs.tagged.and_then(|_| Tag::find(&generics));
+    let struct_def = generate_struct(&name, &generics, &vis, &inner, tag.as_ref(), serde);
+    let microtype_impl =
+        generate_validated_microtype_impl(&name, &generics, &inner, &check, &error, tag.as_ref());
+    let range_impl =
+        range.map(|range| generate_range_impl(&name, &generics, &inner, range, tag.as_ref()));
+    let one_of_impl = special_attrs
+        .one_of
+        .as_ref()
+        .map(|one_of| one_of_impl(&name, &generics, one_of));
+    let checksum_impl = special_attrs
+        .checksum
+        .as_ref()
+        .map(|checksum| checksum_impl(&name, &generics, &inner, checksum, int));
+    let try_from_impl = generate_try_from_impl(&name, &generics, &inner, &error);
+    let deref_impl = generate_deref_impl(&name, &generics, &inner, overrides.deref());
+    // prefixed IDs use the prefixed form, rather than the inner value
+    let serde_attrs = serde_derives(serde && special_attrs.prefixed_id.is_none());
+    let deserialize_impl = deserialize_impl(
+        &name,
+        &generics,
+        &inner,
+        serde && special_attrs.prefixed_id.is_none(),
+    );
+    let tagged_impls = tag
+        .as_ref()
+        .map(|tag| tagged_impls(&name, &generics, &inner, tag));
+
+    let diesel_impls = special_attrs
+        .diesel_type
+        .map(|sql_type| diesel_impl_validated(&sql_type, &inner, &name, &generics));
+
+    let type_specific_impls = match (&special_attrs.type_annotation, &special_attrs.prefixed_id) {
+        (None, Some(prefixed_id)) => prefixed_id_impls(&name, &generics, prefixed_id, serde),
+        // `#[one_of(...)]` implies `#[string]`
+        (None, None) if special_attrs.one_of.is_some() => {
+            validated_string_impls(&name, &generics, &inner, &error, &StringAttr::default())
+        }
+        (None, None) => quote! {},
+        (Some(TypeAnnotation::String(string)), _) => {
+            validated_string_impls(&name, &generics, &inner, &error, string)
+        }
+        (Some(TypeAnnotation::Int(int)), _) => {
+            validated_int_impls(&name, &generics, &inner, &error, int)
+        }
+        (Some(TypeAnnotation::Float(float)), _) => {
+            validated_float_impls(&name, &generics, &inner, &error, float)
+        }
+    };
+
+    // a hand-written struct already has its attributes
+    let struct_def = match hand_written {
+        true => quote! {},
+        false => quote! {
+            #(#attrs)*
+            #serde_attrs
+            #struct_def
+        },
+    };
+
+    quote! {
+        #struct_def
+
+        #microtype_impl
+        #range_impl
+        #one_of_impl
+        #checksum_impl
+
+        #try_from_impl
+        #deref_impl
+        #deserialize_impl
+        #tagged_impls
+        #type_specific_impls
+        #diesel_impls
+    }
+}
@@ -5,9 +5,9 @@
 
 use codegen::codegen;
 use parse::MicrotypeMacro;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, DeriveInput};
 
-use crate::model::flatten;
+use crate::model::{flatten, from_derive_input};
 
 extern crate proc_macro;
 
@@ -52,3 +52,46 @@ pub fn microtype(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
     codegen(microtypes).into()
 }
 
+/// Derive macro to generate microtype impls for a hand-written newtype struct
+///
+/// This generates the same impls as `microtype!`, but since the struct already exists, derives
+/// (e.g. `serde`) and `#[repr(transparent)]` must be added by hand
+///
+/// Example usage:
+/// ```ignore
+/// # use microtype::Microtype;
+/// /// An email address
+/// #[derive(Debug, Clone, Microtype)]
+/// #[string]
+/// pub struct Email(String);
+///
+/// impl Email {
+///     pub fn domain(&self) -> Option<&str> {
+///         self.0.split_once('@').map(|(_, domain)| domain)
+///     }
+/// }
+/// ```
+#[proc_macro_derive(
+    Microtype,
+    attributes(
+        secret,
+        string,
+        int,
+        float,
+        validate,
+        microtype,
+        ops,
+        normalize,
+        one_of,
+        prefixed_id,
+        id,
+        checksum
+    )
+)]
+pub fn derive_microtype(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    match from_derive_input(input) {
+        Ok(microtype) => codegen(vec![microtype]).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -1,12 +1,19 @@
-use syn::{Attribute, Ident, Type, Visibility};
+use syn::{
+    Attribute, Data, DataStruct, DeriveInput, Error, Fields, Generics, Ident, Result, Type,
+    Visibility,
+};
 
 use crate::parse::MicrotypeMacro;
 
 pub struct Microtype {
     pub inner: Type,
     pub name: Ident,
+    pub generics: Generics,
     pub vis: Visibility,
     pub attrs: Vec<Attribute>,
+    /// The struct already exists (i.e. it came from `#[derive(Microtype)]`), so only impls should
+    /// be generated
+    pub hand_written: bool,
 }
 
 pub fn flatten(microtype_macro: MicrotypeMacro) -> Vec<Microtype> {
@@ -20,7 +27,9 @@ pub fn flatten(microtype_macro: MicrotypeMacro) -> Vec<Microtype> {
                 attrs,
                 inner: decl.inner.clone(),
                 name: attr_ident.ident,
+                generics: attr_ident.generics,
                 vis: decl.vis.clone(),
+                hand_written: false,
             };
 
             result.push(microtype);
@@ -30,10 +39,35 @@ pub fn flatten(microtype_macro: MicrotypeMacro) -> Vec<Microtype> {
     result
 }
 
+/// For secrets, `inner` is still the type of the field (i.e. `Secret<T>`)
+pub fn from_derive_input(input: DeriveInput) -> Result<Microtype> {
+    let inner = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) if fields.unnamed.len() == 1 => fields.unnamed.into_iter().next().unwrap().ty,
+        _ => {
+            return Err(Error::new_spanned(
+                input.ident,
+                "`#[derive(Microtype)]` only supports tuple structs with a single field",
+            ))
+        }
+    };
+
+    Ok(Microtype {
+        inner,
+        name: input.ident,
+        generics: input.generics,
+        vis: input.vis,
+        attrs: input.attrs,
+        hand_written: true,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use quote::ToTokens;
-    use syn::parse_str;
+    use syn::{parse_quote, parse_str};
 
     use super::*;
 
@@ -57,4 +91,39 @@ mod tests {
         assert_eq!(second.inner.to_token_stream().to_string(), "String");
         assert_eq!(second.name.to_string(), "Username");
     }
+
+    #[test]
+    fn builds_microtype_from_derive_input() {
+        let input: DeriveInput = parse_quote! {
+            #[string]
+            pub struct Email<'a>(&'a str);
+        };
+        let microtype = from_derive_input(input).unwrap();
+
+        assert_eq!(microtype.attrs.len(), 1);
+        assert_eq!(microtype.inner.to_token_stream().to_string(), "& 'a str");
+        assert_eq!(microtype.name.to_string(), "Email");
+        assert_eq!(microtype.generics.params.len(), 1);
+        assert!(microtype.hand_written);
+    }
+
+    #[test]
+    fn derive_input_must_have_single_field() {
+        let input: DeriveInput = parse_quote!(
+            struct Email(String, String);
+        );
+        assert!(from_derive_input(input).is_err());
+
+        let input: DeriveInput = parse_quote!(
+            struct Email {
+                email: String,
+            }
+        );
+        assert!(from_derive_input(input).is_err());
+
+        let input: DeriveInput = parse_quote!(
+            enum Email {}
+        );
+        assert!(from_derive_input(input).is_err());
+    }
 }
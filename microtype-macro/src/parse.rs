@@ -2,23 +2,9 @@ use syn::{
     braced,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    Attribute, Ident, LitStr, Result, Token, Type, Visibility,
+    Attribute, Generics, Ident, Result, Token, Type, Visibility, WhereClause, WherePredicate,
 };
 
-/// The `= "foo::Bar"` part of the diesel type attribute
-pub struct DieselTypeAttr {
-    pub ty: Ident,
-}
-
-impl Parse for DieselTypeAttr {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let _: Token![=] = input.parse()?;
-        let s: LitStr = input.parse()?;
-        let ty = Ident::new(&s.value(), s.span());
-        Ok(Self { ty })
-    }
-}
-
 /// The entire invocation of the macro
 pub struct MicrotypeMacro(pub Vec<MicrotypeDecl>);
 
@@ -59,20 +45,56 @@ impl Parse for MicrotypeDecl {
     }
 }
 
-/// Identifier with 0 or more attributes
+/// Identifier with 0 or more attributes, and optional generics (e.g. `Items<T> where T: Clone`)
 pub struct AttrIdent {
     pub attributes: Vec<Attribute>,
     pub ident: Ident,
+    pub generics: Generics,
 }
 
 impl Parse for AttrIdent {
     fn parse(input: ParseStream) -> Result<Self> {
         let attributes = input.call(Attribute::parse_outer)?;
         let ident = input.parse()?;
-        Ok(Self { attributes, ident })
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = parse_where_clause(input)?;
+        Ok(Self {
+            attributes,
+            ident,
+            generics,
+        })
     }
 }
 
+/// `syn`'s `WhereClause` parser treats every comma as the start of another predicate, but here a
+/// comma can also separate two microtypes, so only continue if a predicate actually follows
+fn parse_where_clause(input: ParseStream) -> Result<Option<WhereClause>> {
+    if !input.peek(Token![where]) {
+        return Ok(None);
+    }
+
+    let where_token = input.parse()?;
+    let mut predicates = Punctuated::new();
+
+    loop {
+        predicates.push_value(input.parse::<WherePredicate>()?);
+
+        let fork = input.fork();
+        let next_is_predicate =
+            fork.parse::<Token![,]>().is_ok() && fork.parse::<WherePredicate>().is_ok();
+        if !next_is_predicate {
+            break;
+        }
+
+        predicates.push_punct(input.parse()?);
+    }
+
+    Ok(Some(WhereClause {
+        where_token,
+        predicates,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use syn::parse_str;
@@ -86,6 +108,33 @@ mod tests {
         assert_eq!(attr_ident.ident.to_string(), "asdf");
     }
 
+    #[test]
+    fn parse_attr_ident_with_generics() {
+        let attr_ident: AttrIdent = parse_str("Items<'a, T: Clone> where T: Default").unwrap();
+        assert_eq!(attr_ident.ident.to_string(), "Items");
+        assert_eq!(attr_ident.generics.params.len(), 2);
+        assert_eq!(
+            attr_ident.generics.where_clause.unwrap().predicates.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn parse_where_clauses_in_list() {
+        let microtype_decl: MicrotypeDecl = parse_str(
+            "Vec<T> { Items<T> where T: Clone, Vec<T>: Default, Other<T> where T: Copy, Plain }",
+        )
+        .unwrap();
+        let idents = &microtype_decl.idents;
+        assert_eq!(idents.len(), 3);
+
+        let first = idents[0].generics.where_clause.as_ref().unwrap();
+        assert_eq!(first.predicates.len(), 2);
+        assert_eq!(idents[1].ident.to_string(), "Other");
+        assert_eq!(idents[2].ident.to_string(), "Plain");
+        assert!(idents[2].generics.params.is_empty());
+    }
+
     #[test]
     fn parse_microtype_decl() {
         let microtype_decl: MicrotypeDecl =
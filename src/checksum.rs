@@ -0,0 +1,223 @@
+//! The runtime half of `#[checksum(...)]`
+//!
+//! Values are treated as a sequence of digits, where letters (only allowed by ISO 7064) are the
+//! digits 10 to 35, as in IBANs and LEIs. Integers use their decimal digits
+
+use crate::{ChecksumAlgorithm, ChecksumErrorKind};
+
+/// The decimal digits of `u128::MAX`
+const MAX_DIGITS: usize = 39;
+
+/// The check digits of a payload, which are ASCII digits
+pub struct CheckDigits {
+    digits: [u8; 2],
+    len: usize,
+}
+
+impl CheckDigits {
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.digits[..self.len]).expect("check digits are ASCII")
+    }
+}
+
+fn digit_value(algorithm: ChecksumAlgorithm, c: u8) -> Result<u8, ChecksumErrorKind> {
+    match (algorithm, c) {
+        (_, b'0'..=b'9') => Ok(c - b'0'),
+        (ChecksumAlgorithm::Iso7064Mod97, b'A'..=b'Z') => Ok(c - b'A' + 10),
+        _ => Err(ChecksumErrorKind::InvalidCharacter),
+    }
+}
+
+/// Writes the decimal digits of `n` to the end of `buf`, and returns them
+fn decimal_digits(mut n: u128, buf: &mut [u8; MAX_DIGITS]) -> &[u8] {
+    let mut start = MAX_DIGITS;
+    loop {
+        start -= 1;
+        buf[start] = (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            return &buf[start..];
+        }
+    }
+}
+
+/// The Luhn sum, where every second digit from the right is doubled, starting with the last digit
+/// if `double_last` is set
+fn luhn_sum(digits: impl DoubleEndedIterator<Item = u8>, double_last: bool) -> u32 {
+    digits
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| match (i % 2 == 1) != double_last {
+            true if digit >= 5 => u32::from(digit) * 2 - 9,
+            true => u32::from(digit) * 2,
+            false => u32::from(digit),
+        })
+        .sum()
+}
+
+/// The value mod 97, where a letter counts as two decimal digits
+fn mod97(digits: impl Iterator<Item = u8>) -> u32 {
+    digits.fold(0, |acc, digit| match digit {
+        0..=9 => (acc * 10 + u32::from(digit)) % 97,
+        _ => (acc * 100 + u32::from(digit)) % 97,
+    })
+}
+
+fn validate(
+    algorithm: ChecksumAlgorithm,
+    digits: impl DoubleEndedIterator<Item = u8> + ExactSizeIterator,
+) -> Result<(), ChecksumErrorKind> {
+    let valid = match algorithm {
+        ChecksumAlgorithm::Luhn if digits.len() < 2 => return Err(ChecksumErrorKind::TooShort),
+        ChecksumAlgorithm::Iso7064Mod97 if digits.len() < 3 => {
+            return Err(ChecksumErrorKind::TooShort)
+        }
+        // `is_multiple_of` needs Rust 1.87
+        #[allow(clippy::manual_is_multiple_of)]
+        ChecksumAlgorithm::Luhn => luhn_sum(digits, false) % 10 == 0,
+        ChecksumAlgorithm::Iso7064Mod97 => mod97(digits) == 1,
+    };
+
+    match valid {
+        true => Ok(()),
+        false => Err(ChecksumErrorKind::Mismatch),
+    }
+}
+
+/// The check digits as a number, and how many decimal digits it takes up
+fn check_digits(
+    algorithm: ChecksumAlgorithm,
+    payload: impl DoubleEndedIterator<Item = u8>,
+) -> (u8, usize) {
+    match algorithm {
+        ChecksumAlgorithm::Luhn => (((10 - luhn_sum(payload, true) % 10) % 10) as u8, 1),
+        ChecksumAlgorithm::Iso7064Mod97 => ((98 - mod97(payload) * 100 % 97) as u8, 2),
+    }
+}
+
+pub fn validate_str(algorithm: ChecksumAlgorithm, value: &str) -> Result<(), ChecksumErrorKind> {
+    for c in value.bytes() {
+        digit_value(algorithm, c)?;
+    }
+    validate(
+        algorithm,
+        value
+            .bytes()
+            .map(|c| digit_value(algorithm, c).expect("checked above")),
+    )
+}
+
+pub fn validate_int(algorithm: ChecksumAlgorithm, value: u128) -> Result<(), ChecksumErrorKind> {
+    let mut buf = [0; MAX_DIGITS];
+    validate(algorithm, decimal_digits(value, &mut buf).iter().copied())
+}
+
+/// The check digits to append to `payload`, which must not be empty
+pub fn str_check_digits(
+    algorithm: ChecksumAlgorithm,
+    payload: &str,
+) -> Result<CheckDigits, ChecksumErrorKind> {
+    if payload.is_empty() {
+        return Err(ChecksumErrorKind::TooShort);
+    }
+    for c in payload.bytes() {
+        digit_value(algorithm, c)?;
+    }
+
+    let payload = payload
+        .bytes()
+        .map(|c| digit_value(algorithm, c).expect("checked above"));
+    let (check, len) = check_digits(algorithm, payload);
+    let digits = match len {
+        1 => [b'0' + check, 0],
+        _ => [b'0' + check / 10, b'0' + check % 10],
+    };
+
+    Ok(CheckDigits { digits, len })
+}
+
+/// `payload` followed by its check digits, or `None` if that overflows
+pub fn with_check_digits(algorithm: ChecksumAlgorithm, payload: u128) -> Option<u128> {
+    let mut buf = [0; MAX_DIGITS];
+    let (check, len) = check_digits(algorithm, decimal_digits(payload, &mut buf).iter().copied());
+
+    payload
+        .checked_mul(10u128.pow(len as u32))?
+        .checked_add(u128::from(check))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luhn() {
+        assert_eq!(validate_int(ChecksumAlgorithm::Luhn, 79927398713), Ok(()));
+        assert_eq!(validate_str(ChecksumAlgorithm::Luhn, "79927398713"), Ok(()));
+        assert_eq!(
+            validate_int(ChecksumAlgorithm::Luhn, 79927398710),
+            Err(ChecksumErrorKind::Mismatch)
+        );
+        assert_eq!(
+            validate_str(ChecksumAlgorithm::Luhn, "79927398731"),
+            Err(ChecksumErrorKind::Mismatch)
+        );
+        assert_eq!(
+            validate_str(ChecksumAlgorithm::Luhn, "7992739871A"),
+            Err(ChecksumErrorKind::InvalidCharacter)
+        );
+        assert_eq!(
+            validate_int(ChecksumAlgorithm::Luhn, 7),
+            Err(ChecksumErrorKind::TooShort)
+        );
+    }
+
+    #[test]
+    fn luhn_check_digits() {
+        let digits = str_check_digits(ChecksumAlgorithm::Luhn, "7992739871").unwrap();
+        assert_eq!(digits.as_str(), "3");
+        assert_eq!(
+            with_check_digits(ChecksumAlgorithm::Luhn, 7992739871),
+            Some(79927398713)
+        );
+        assert_eq!(with_check_digits(ChecksumAlgorithm::Luhn, u128::MAX), None);
+    }
+
+    #[test]
+    fn mod97() {
+        // the IBAN GB82 WEST 1234 5698 7654 32, with the country code and check digits moved to
+        // the end
+        let iban = "WEST12345698765432GB82";
+        assert_eq!(validate_str(ChecksumAlgorithm::Iso7064Mod97, iban), Ok(()));
+        assert_eq!(
+            validate_str(ChecksumAlgorithm::Iso7064Mod97, "WEST12345698765423GB82"),
+            Err(ChecksumErrorKind::Mismatch)
+        );
+        assert_eq!(
+            validate_str(ChecksumAlgorithm::Iso7064Mod97, "west12345698765432GB82"),
+            Err(ChecksumErrorKind::InvalidCharacter)
+        );
+        assert_eq!(
+            validate_int(ChecksumAlgorithm::Iso7064Mod97, 97),
+            Err(ChecksumErrorKind::TooShort)
+        );
+    }
+
+    #[test]
+    fn mod97_check_digits() {
+        let digits =
+            str_check_digits(ChecksumAlgorithm::Iso7064Mod97, "WEST12345698765432GB").unwrap();
+        assert_eq!(digits.as_str(), "82");
+        // single digit check values are zero-padded
+        let digits = str_check_digits(ChecksumAlgorithm::Iso7064Mod97, "30").unwrap();
+        assert_eq!(digits.as_str(), "08");
+        assert_eq!(
+            with_check_digits(ChecksumAlgorithm::Iso7064Mod97, 30),
+            Some(3008)
+        );
+        assert_eq!(
+            str_check_digits(ChecksumAlgorithm::Iso7064Mod97, "").map(|d| d.len),
+            Err(ChecksumErrorKind::TooShort)
+        );
+    }
+}
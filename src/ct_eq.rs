@@ -0,0 +1,60 @@
+//! [`ConstantTimeEq`] for the usual inner types of secrets, using `subtle`
+
+#[cfg(feature = "alloc")]
+use crate::__private::alloc::{string::String, vec::Vec};
+use crate::{ConstantTimeEq, SecretMicrotype};
+
+impl<T: SecretMicrotype> ConstantTimeEq for T
+where
+    T::Inner: ConstantTimeEq,
+{
+    fn ct_eq(&self, other: &Self) -> bool {
+        self.expose_secret().ct_eq(other.expose_secret())
+    }
+}
+
+impl ConstantTimeEq for [u8] {
+    fn ct_eq(&self, other: &Self) -> bool {
+        subtle::ConstantTimeEq::ct_eq(self, other).into()
+    }
+}
+
+impl<const N: usize> ConstantTimeEq for [u8; N] {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self[..].ct_eq(&other[..])
+    }
+}
+
+impl ConstantTimeEq for str {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ConstantTimeEq for Vec<u8> {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self[..].ct_eq(&other[..])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ConstantTimeEq for String {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self.as_str().ct_eq(other.as_str())
+    }
+}
+
+macro_rules! int_impls {
+    ($($int:ty),*) => {
+        $(
+            impl ConstantTimeEq for $int {
+                fn ct_eq(&self, other: &Self) -> bool {
+                    subtle::ConstantTimeEq::ct_eq(self, other).into()
+                }
+            }
+        )*
+    };
+}
+
+int_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
@@ -112,6 +112,124 @@
 //! ```
 //!
 //!
+//! ## Private fields
+//!
+//! By default, the wrapped value is a `pub` field, so a microtype can be constructed directly
+//! (e.g. `UserId(string)`). This can make it hard to audit where values come from. Use
+//! `#[private]` (on a block, or on a single microtype) to make the field private, so that values
+//! can only be created and accessed via the [`Microtype`] and [`MicrotypeMut`] APIs outside of the
+//! defining module:
+//! ```
+//! mod ids {
+//!     microtype::microtype! {
+//!         #[private]
+//!         pub String {
+//!             UserId
+//!         }
+//!     }
+//! }
+//!
+//! use microtype::Microtype;
+//!
+//! fn main() {
+//!     // let user_id = ids::UserId("id".into());  // Error: field is private
+//!     let user_id = ids::UserId::new("id".into());
+//!     assert_eq!(user_id.inner(), "id");
+//! }
+//! ```
+//!
+//! ## Generics
+//!
+//! Microtypes can have generic parameters (including lifetimes) and a where clause, which is
+//! useful for typed containers or borrowed views of other data:
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     Vec<T> {
+//!         Items<T>
+//!     }
+//!
+//!     &'a str {
+//!         UserIdRef<'a>
+//!     }
+//!
+//!     std::collections::HashMap<K, V> {
+//!         Lookup<K, V> where K: std::hash::Hash + Eq
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let items = Items::new(vec![1, 2, 3]);
+//!     assert_eq!(items.len(), 3);
+//!
+//!     let id = String::from("user");
+//!     let id_ref = UserIdRef::new(&id);
+//!     assert_eq!(id_ref.into_inner(), "user");
+//! }
+//! ```
+//! Generated implementations only apply when the parameters satisfy the bounds they need. For
+//! example, `Items<T>` only implements `Serialize` when `T: Serialize`.
+//!
+//! ## Tagged microtypes
+//!
+//! Declaring a separate ID type for every entity can get noisy. Instead, a `#[tagged]` microtype
+//! has a type parameter that is only used as a marker (its last type parameter), so a single
+//! declaration can be used for all of them:
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[tagged]
+//!     i64 {
+//!         Id<T>
+//!     }
+//! }
+//!
+//! struct User;
+//! struct Order;
+//!
+//! fn main() {
+//!     let user_id: Id<User> = Id::new(123);
+//!     let order_id: Id<Order> = Id::new(123);
+//!     // assert_eq!(user_id, order_id);  // Error: mismatched types
+//!     assert_eq!(user_id.into_inner(), order_id.into_inner());
+//! }
+//! ```
+//! The tag is stored as a `PhantomData<fn() -> T>`, so it doesn't affect the layout of the
+//! microtype. Tagged microtypes implement `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq`,
+//! `PartialOrd`, `Ord` and `Hash` whenever the inner type does, without requiring anything of the
+//! tag (so `#[derive(...)]`-ing these traits is an error). Secret microtypes only get `Clone` and
+//! `Debug`.
+//!
+//! ## Hand-written microtypes
+//!
+//! Sometimes a microtype needs to be written by hand (e.g. to add docs or inherent methods). Use
+//! `#[derive(Microtype)]` on a tuple struct with a single field to generate the same impls as
+//! [`microtype!`]:
+//! ```
+//! use microtype::Microtype;
+//!
+//! /// An email address
+//! #[derive(Debug, Clone, Microtype)]
+//! #[string]
+//! pub struct Email(String);
+//!
+//! impl Email {
+//!     pub fn domain(&self) -> Option<&str> {
+//!         self.0.split_once('@').map(|(_, domain)| domain)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let email = Email::from("user@example.com");
+//!     assert_eq!(email.domain(), Some("example.com"));
+//! }
+//! ```
+//! `#[secret]`, `#[string]`, `#[int]`, `#[validate(...)]` and `#[microtype(...)]` are supported.
+//! Since the struct already exists, nothing is added to it: derives (including `Serialize` and
+//! `Deserialize`, except for validated microtypes) and `#[repr(transparent)]` must be written by
+//! hand. A secret microtype's field must be a [`secrecy::Secret`], and its `Debug` impl is not
+//! replaced in tests.
+//!
 //! ## Secrets
 //!
 //! Some types may be considered "sensitive" (for example: passwords, session tokens, etc).
@@ -135,21 +253,21 @@
 //! Secret microtypes don't implement [`Microtype`], instead they implement
 //! [`SecretMicrotype`], which has a much more restrictive API:
 //!  - Mutable and owned access to the inner data is not possible, it is only possible to get a
-//!  shared reference to the inner data via [`secrecy::ExposeSecret::expose_secret`], which makes
-//!  accesses easier to audit.
+//!    shared reference to the inner data via [`secrecy::ExposeSecret::expose_secret`], which makes
+//!    accesses easier to audit.
 //!  - They `#[derive(Debug, Clone)]` (and optionally `Serialize` and `Deserialize`) but do not support adding extra derive macros.
 //!
 //! Internally, they wrap the contained data in [`secrecy::Secret`], which provides some nice
 //! safety features. In particular:
 //!  - The debug representation is redacted. This is can prevent against accidentally leaking
-//!  data to logs, but it still *has* a `Debug` implementation (so you can still
-//!  `#[derive(Debug)]` on structs which contain secret data)
+//!    data to logs, but it still *has* a `Debug` implementation (so you can still
+//!    `#[derive(Debug)]` on structs which contain secret data)
 //!  - Data is zeroized after use, meaning the underlying data is overwritten with 0s, which
-//!  ensures sensitive data exists in memory only for as long as is needed. (Caveat: not all types
-//!  have perfect zeroize implementations. Notably `Vec` (and `String`) will not be able to zeroize
-//!  previous allocations)
+//!    ensures sensitive data exists in memory only for as long as is needed. (Caveat: not all types
+//!    have perfect zeroize implementations. Notably `Vec` (and `String`) will not be able to zeroize
+//!    previous allocations)
 //!  - when using `serde`, secret microtypes do not implement `Serialize`, to avoid accidentally
-//!  leaking secret data
+//!    leaking secret data
 //!
 //! ## Serializable Secrets
 //!
@@ -174,6 +292,58 @@
 //! }
 //! ```
 //!
+//! ## Comparing Secrets
+//!
+//! Secret microtypes don't implement `PartialEq` (except in tests, with `test_impls`), and
+//! comparing the exposed values with `==` is unsafe for things like API keys, since it can return
+//! as soon as a byte differs, letting an attacker guess the secret one byte at a time from how long
+//! it takes. With the `ct_eq` feature, secret microtypes implement `ConstantTimeEq` instead,
+//! which compares them in a time that only depends on their lengths:
+//!
+//! ```ignore
+//! # use microtype::{microtype, ConstantTimeEq, SecretMicrotype};
+//! microtype! {
+//!     #[secret]
+//!     String {
+//!         ApiKey
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let key = ApiKey::new("sk_live_123".to_string());
+//!     assert!(key.ct_eq(&ApiKey::new("sk_live_123".to_string())));
+//! }
+//! ```
+//!
+//! This works for any secret whose inner type implements `ConstantTimeEq`, which includes
+//! `String`, `str`, `Vec<u8>`, `[u8]`, `[u8; N]` and the integer primitives. It can be
+//! implemented for other types too.
+//!
+//! To use a secret as e.g. a `HashMap` key, mark it `#[secret(hash)]` (this also requires `std`).
+//! `PartialEq` and `Eq` then use `ConstantTimeEq`, and `Hash` first hashes the secret with
+//! SipHash, using a key that is chosen randomly once per process, and only passes that hash on to
+//! the map's hasher. This means the secret never goes through a hasher that might be predictable,
+//! and the hashes of secrets can't be compared between processes. The inner type's `Hash` must
+//! agree with its `ConstantTimeEq`, which is true for the types above. With `test_impls`, the
+//! `PartialEq` used in tests is this one.
+//!
+//! ```ignore
+//! # use std::collections::HashMap;
+//! # use microtype::{microtype, SecretMicrotype};
+//! microtype! {
+//!     #[secret(hash)]
+//!     String {
+//!         SessionToken
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut sessions = HashMap::new();
+//!     sessions.insert(SessionToken::new("abc".to_string()), "alice");
+//!     assert_eq!(sessions[&SessionToken::new("abc".to_string())], "alice");
+//! }
+//! ```
+//!
 //! ## Type Hints
 //!
 //! Proc-macros are run before type information is available, so can only use the text of the
@@ -182,11 +352,58 @@
 //! as wrapping a well-known type, to generate more helpful implementations for you:
 //!
 //! - If the wrapped type is a `String`, you can use `#[string]` to provide a few extra
-//! implementations (e.g. `FromStr`, `From<&str>`, `Display`)
+//!   implementations (e.g. `FromStr`, `From<&str>`, `Display`)
 //! - If the wrapped type is an integer type, you can use `#[int]` to provide other extra
-//! implementations: various `fmt` traits (e.g. `UpperHex`, etc), as well as arithmetic traits
-//! (`Add`, `AddAssign`, etc). These are incomplete, please open a PR/issue if there are implementations
-//! you rely on that are missing
+//!   implementations: various `fmt` traits (e.g. `UpperHex`, etc), as well as arithmetic traits
+//!   (`Add`, `AddAssign`, etc). These are incomplete, please open a PR/issue if there are implementations
+//!   you rely on that are missing
+//! - If the wrapped type is `f32` or `f64`, you can use `#[float]` to provide `Display`,
+//!   `LowerExp`, `UpperExp`, `FromStr` (with `ParseFloatError`), and arithmetic traits with `T`,
+//!   `&T` and the inner type, as well as `Neg`, `Sum` and `Product`
+//!
+//! Arithmetic on `#[int]` microtypes can be tuned with `#[int(key, key = bool, ...)]`, where a
+//! bare key is the same as `key = true`:
+//!
+//! | Key | Default | Generates |
+//! |---|---|---|
+//! | `scalar_ops` | `true` | `T op Inner` and `T op= Inner` (e.g. `price * 2`, `count += 1`) |
+//! | `ref_ops` | `true` | `T op &T`, `&T op &T` and `T op= &T` (requires `Inner: Copy`) |
+//! | `neg` | signed primitives only | `Neg` |
+//! | `bit_ops` | `false` | `BitAnd`, `BitOr`, `BitXor`, `Not`, `Shl` and `Shr` |
+//! | `iter` | `true` | `Sum` and `Product`, over both `T` and `&T` |
+//! | `overflow` | none | see below |
+//!
+//! When the inner type is an integer primitive, `#[int]` microtypes also get inherent
+//! `checked_*`, `saturating_*`, `wrapping_*` and `overflowing_*` methods, as well as `pow` and
+//! (for signed types) `abs`. These return the microtype rather than the inner type, e.g.
+//! `Cents::checked_add(self, rhs: Self) -> Option<Cents>`. Inherent methods take priority over
+//! the inner type's methods reached through `Deref`, so with `deref_impls`, `cents.pow(2)` is also
+//! a `Cents` rather than a `u64`.
+//!
+//! By default, the operators behave like the inner type's, so panic on overflow in debug builds
+//! and wrap in release builds. `#[int(overflow = "...")]` changes this (again, only for integer
+//! primitives):
+//!  - `"saturating"` and `"wrapping"` use the corresponding methods, including for `Sum`,
+//!    `Product` and the `*Assign` operators
+//!  - `"checked"` operators return `Option<T>`, and `Sum`/`Product` are implemented for
+//!    `Option<T>` instead. There are no `*Assign` operators, since they can't report the overflow
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!   #[derive(Clone, Copy)]
+//!   #[int(overflow = "checked")]
+//!   u64 {
+//!     Cents
+//!   }
+//! }
+//!
+//! fn main() {
+//!   let max = Cents::new(u64::MAX);
+//!   assert!((max + 1).is_none());
+//!   assert_eq!(max.saturating_add(Cents::new(1)).into_inner(), u64::MAX);
+//! }
+//! ```
 //!
 //! For example:
 //! ```
@@ -209,25 +426,612 @@
 //!
 //!   println!("{email}");  
 //!   println!("display: {num}, hex: {num:x}");
+//!
+//!   let total: Num = [&num + &num, -&num, num * 2].iter().sum();
+//!   assert_eq!(total.into_inner(), 369);
 //! }
 //! ``` 
 //!
 //!
+//! `#[float(...)]` accepts `scalar_ops`, `ref_ops` and `iter` with the same meaning as for
+//! `#[int(...)]`, as well as a few float-specific keys, which need an `f32` or `f64` inner type:
+//!  - `total_ord` implements `PartialEq`, `Eq`, `PartialOrd` and `Ord` using `total_cmp`, so they
+//!    can't be derived as well. Note that this means `0.0 != -0.0`, and NaN is equal to itself
+//!  - `hash` implements `Hash` consistently with `total_ord`, which it requires
+//!  - `finite` rejects NaN and infinity, making the microtype validated, with [`NotFiniteError`] as
+//!    the error type. Like bounded integers (see below), arithmetic operators return `Option<T>`,
+//!    which is `None` if the result isn't finite
+//!
+//! ```
+//! # use microtype::*;
+//! # use std::collections::HashSet;
+//! microtype! {
+//!   #[derive(Debug, Clone, Copy)]
+//!   #[float(finite, total_ord, hash)]
+//!   f64 {
+//!     Latitude
+//!   }
+//! }
+//!
+//! fn main() {
+//!   assert!(Latitude::try_new(f64::NAN).is_err());
+//!   assert!("inf".parse::<Latitude>().is_err());
+//!
+//!   let lat = Latitude::try_new(51.5).unwrap();
+//!   assert_eq!((lat * 2.0).map(Latitude::into_inner), Some(103.0));
+//!   assert_eq!(lat * f64::INFINITY, None);
+//!
+//!   let set: HashSet<_> = [lat, lat].into_iter().collect();
+//!   assert_eq!(set.len(), 1);
+//!   assert!(Latitude::try_new(-0.0).unwrap() < Latitude::try_new(0.0).unwrap());
+//! }
+//! ```
+//!
+//! `#[string(case_insensitive)]` implements `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` so
+//! that they ignore ASCII case, while `Display`, `AsRef<str>`, etc. still give the original text.
+//! `#[string(case_insensitive = "unicode")]` folds all Unicode letters instead (e.g. `"straße"`
+//! equals `"STRASSE"`). Since these traits are generated, they can't also be derived:
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!   #[derive(Debug, Clone)]
+//!   #[string(case_insensitive)]
+//!   String {
+//!     HeaderName
+//!   }
+//! }
+//!
+//! fn main() {
+//!   let header = HeaderName::from("Content-Type");
+//!   assert_eq!(header, HeaderName::from("content-type"));
+//!   assert_eq!(header.to_string(), "Content-Type");
+//! }
+//! ```
+//!
+//! Type hints can also be used with secret microtypes, but only implementations that don't leak
+//! the secret are generated. For example, a `#[secret] #[int]` microtype implements `FromStr`, but
+//! not `Display` or `LowerHex`:
+//! ```
+//! # use microtype::*;
+//! # use microtype::secrecy::ExposeSecret;
+//! microtype! {
+//!   #[secret]
+//!   #[int]
+//!   u32 {
+//!     Pin
+//!   }
+//! }
+//!
+//! fn main() {
+//!   let pin: Pin = "1234".parse().unwrap();
+//!   assert_eq!(*pin.expose_secret(), 1234);
+//! }
+//! ```
+//!
+//! ## Validation
+//!
+//! Some microtypes have invariants that the inner type can't express (e.g. "non-empty" or "valid
+//! email"). You can use `#[validate(path::to::fn)]` to run a validator whenever a value is
+//! created. The validator takes a reference to the inner value and returns a `Result<(), E>`. By
+//! default, `E` is `&'static str`, but it can be changed with `#[validate(path::to::fn, error = E)]`.
+//! `Deserialize` and `FromSql` report a failed validation using its `Display` impl, so with the
+//! `serde` or `diesel` features, `E` must implement `Display`
+//!
+//! Validated microtypes don't implement [`Microtype`], instead they implement
+//! [`ValidatedMicrotype`]:
+//!  - `new` and `From<Inner>` are replaced with `try_new` and `TryFrom<Inner>`
+//!  - the inner field is private, and `inner_mut` and `DerefMut` are not available, since they
+//!    could be used to break the invariant
+//!  - `FromStr`, `Deserialize` and `FromSql` implementations also run the validator
+//!  - `#[int]` and `#[float]` microtypes don't get arithmetic implementations, so `#[int(...)]`
+//!    and `#[float(...)]` options aren't allowed
+//!
+//! ```
+//! # use microtype::*;
+//! fn not_empty(s: &String) -> Result<(), &'static str> {
+//!     match s.is_empty() {
+//!         true => Err("must not be empty"),
+//!         false => Ok(()),
+//!     }
+//! }
+//!
+//! microtype! {
+//!     #[string]
+//!     #[validate(not_empty)]
+//!     String {
+//!         Username
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let username = Username::try_new("alice".to_string()).unwrap();
+//!     assert_eq!(username.inner(), "alice");
+//!
+//!     assert!(Username::try_from("").is_err());
+//!     assert!("".parse::<Username>().is_err());
+//! }
+//! ```
+//!
+//! ## String constraints
+//!
+//! `#[string(...)]` also accepts some common constraints, which are checked whenever a value is
+//! created:
+//!  - `pattern = "..."` - the whole value must match a regex (requires the `regex` feature), so
+//!    `^` and `$` aren't needed. The regex is checked at compile time, and compiled once, the
+//!    first time it's used
+//!  - `min_len = n` and `max_len = n` - the length, in `char`s, must be in range
+//!  - `ascii` - the value may only contain ASCII characters
+//!
+//! Like `#[int(range = ...)]`, these make the microtype validated, with
+//! [`StringConstraintError`] as the error type. It names the microtype and the first
+//! [`StringConstraint`] that was broken. Since the error type is fixed, they can't be combined
+//! with `#[validate(...)]`.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug)]
+//!     #[string(min_len = 3, max_len = 32, ascii)]
+//!     String {
+//!         Username
+//!     }
+//! }
+//!
+//! fn main() {
+//!     assert!(Username::try_from("alice").is_ok());
+//!
+//!     let error = "al".parse::<Username>().unwrap_err();
+//!     assert_eq!(error.constraint, StringConstraint::MinLen(3));
+//!     assert_eq!(error.to_string(), "Username must be at least 3 characters long");
+//! }
+//! ```
+//!
+//! ## Closed sets of strings
+//!
+//! `#[one_of("a", "b", ...)]` only accepts the listed values, for strings that are really enums
+//! (e.g. currency codes). It implies `#[string]`, and makes the microtype validated, with
+//! [`OneOfError`] as the error type, so `FromStr`, `Deserialize` and `FromSql` reject anything else.
+//!
+//! Each value gets an associated `&'static str` constant, named by uppercasing the value and
+//! replacing anything that can't be in an identifier with `_` (e.g. `"en-GB"` becomes `EN_GB`).
+//! `ALL` lists every value. Since they're `&str` constants, they can also be used as patterns.
+//! Values are normalized before they're checked, so `#[string(normalize = [...])]` can be used to
+//! e.g. accept any case.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug)]
+//!     #[one_of("USD", "EUR", "GBP")]
+//!     String {
+//!         Currency
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let currency: Currency = "EUR".parse().unwrap();
+//!     let symbol = match currency.as_ref() {
+//!         Currency::USD => "$",
+//!         Currency::EUR => "€",
+//!         _ => "£",
+//!     };
+//!     assert_eq!(symbol, "€");
+//!
+//!     assert_eq!(Currency::ALL, ["USD", "EUR", "GBP"]);
+//!     assert!(Currency::try_from("JPY").is_err());
+//! }
+//! ```
+//!
+//! ## Prefixed IDs
+//!
+//! `#[prefixed_id("usr")]` creates a `String` ID that is written with a prefix, like
+//! `usr_01H8XGJW...`, so that e.g. an order ID can't be parsed as a user ID. It makes the
+//! microtype validated, with [`PrefixError`] as the error type.
+//!
+//! By default, the prefix is stripped, so the inner value is just the part after the `_`. With
+//! `#[prefixed_id("usr", stored)]`, the inner value keeps the prefix. Either way:
+//!  - `Display`, `FromStr` and serde use the prefixed form, and reject the wrong prefix
+//!  - `try_new`, `TryFrom` and `FromSql` take the inner value, so they expect the prefix only if
+//!    it's stored, and reject it otherwise (so `try_new("usr_abc")` can't display as
+//!    `usr_usr_abc`)
+//!  - the part after the prefix must not be empty
+//!
+//! It also adds a `PREFIX` constant, and requires the `alloc` feature.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug)]
+//!     #[prefixed_id("usr")]
+//!     String {
+//!         UserId
+//!     }
+//!
+//!     #[derive(Debug)]
+//!     #[prefixed_id("ord", stored)]
+//!     String {
+//!         OrderId
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let user: UserId = "usr_01H8XGJW".parse().unwrap();
+//!     assert_eq!(user.inner(), "01H8XGJW");
+//!     assert_eq!(user.to_string(), "usr_01H8XGJW");
+//!
+//!     let order: OrderId = "ord_42".parse().unwrap();
+//!     assert_eq!(order.inner(), "ord_42");
+//!
+//!     assert!("ord_42".parse::<UserId>().is_err());
+//! }
+//! ```
+//!
+//! ## Generated IDs
+//!
+//! `#[id(uuid_v4)]`, `#[id(uuid_v7)]` and `#[id(ulid)]` create an ID with a `generate()`
+//! constructor. The inner type must be `microtype::uuid::Uuid` (requires the `uuid` feature) or
+//! `microtype::ulid::Ulid` (requires the `ulid` feature). They also get:
+//!  - `Display` and `FromStr`, using the usual string form of the inner type
+//!  - serde impls that use the string form, even for binary formats
+//!  - `PartialEq`, `Eq` and `Hash`, which can't also be derived
+//!  - for `uuid_v7` and `ulid`, `PartialOrd` and `Ord`, which follow creation time (to the
+//!    millisecond)
+//!
+//! UUIDs with `#[diesel(sql_type = ...)]` use diesel's own `uuid` support. ULIDs are stored as
+//! text, so `ToSql` needs a backend that writes raw bytes (i.e. not SQLite).
+//!
+//! ```ignore
+//! use microtype::{microtype, ulid::Ulid, uuid::Uuid};
+//!
+//! microtype! {
+//!     #[derive(Debug, Clone, Copy)]
+//!     #[id(uuid_v7)]
+//!     Uuid {
+//!         OrderId
+//!     }
+//!
+//!     #[derive(Debug, Clone, Copy)]
+//!     #[id(ulid)]
+//!     Ulid {
+//!         EventId
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let first = OrderId::generate();
+//!     let second = OrderId::generate();
+//!     assert!(first < second);
+//!     assert_eq!(first.to_string().parse::<OrderId>().unwrap(), first);
+//!
+//!     let event = EventId::generate();
+//!     assert_eq!(event.to_string().len(), 26);
+//! }
+//! ```
+//!
+//! `#[int]` microtypes can instead use `#[id(sequence)]` or `#[id(snowflake(node = ...))]`, which
+//! create IDs with a thread-safe `next()` constructor. They get the usual `#[int]` formatting and
+//! `FromStr` impls, but no arithmetic, so comparison traits and serde work as normal:
+//!  - `sequence` counts up from 1, and panics if it reaches the inner type's `MAX`. Any integer
+//!    primitive except `i128` and `u128` can be used
+//!  - `snowflake` packs 41 bits of milliseconds since an epoch, a 10 bit node ID and a 12 bit
+//!    sequence number into an `i64` or `u64`, so IDs from different nodes never collide, and are
+//!    ordered by creation time. The epoch defaults to Twitter's, and can be set with
+//!    `epoch = ...` (in milliseconds since the Unix epoch). The `node` and `epoch` can be any
+//!    const expression. Snowflakes also get `timestamp_ms()`, `timestamp()`, `node()` and
+//!    `sequence()` accessors, and require the `std` feature
+//!
+//! The counter for each microtype is a `static`, so it is shared by every instantiation of a
+//! generic microtype.
+//!
+//! ```
+//! use microtype::{microtype, Microtype};
+//!
+//! const NODE: u16 = 3;
+//!
+//! microtype! {
+//!     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+//!     #[int]
+//!     #[id(sequence)]
+//!     u32 {
+//!         TicketNumber
+//!     }
+//!
+//!     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+//!     #[int]
+//!     #[id(snowflake(node = NODE))]
+//!     i64 {
+//!         EventId
+//!     }
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(TicketNumber::next().into_inner(), 1);
+//!     assert_eq!(TicketNumber::next().into_inner(), 2);
+//!
+//!     let first = EventId::next();
+//!     let second = EventId::next();
+//!     assert!(first < second);
+//!     assert_eq!(first.node(), NODE);
+//!     assert!(first.timestamp_ms() <= second.timestamp_ms());
+//! }
+//! ```
+//!
+//! ## Public IDs
+//!
+//! `#[int(public_encoding = "sqids")]` hides the number behind a short, reversible string, so e.g.
+//! sequential database IDs can be used in URLs without revealing how many rows there are.
+//! `Display`, `FromStr` and serde use the encoded form, while `new`, `From`, arithmetic and
+//! `ToSql`/`FromSql` still use the number. The other formatting traits (e.g. `LowerHex`) aren't
+//! implemented, since they would show the number. This is obfuscation, not encryption, so don't
+//! rely on it to keep IDs secret.
+//!
+//! The encoding is [Sqids](https://sqids.org) (without a blocklist), so the same IDs can be
+//! decoded by other implementations. `alphabet = "..."` sets the alphabet, which should be unique
+//! to your application, and must have at least 3 unique ASCII characters. `min_length = n` pads
+//! shorter IDs. Parsing fails with [`PublicEncodingError`] unless the string is exactly the
+//! encoding of a number that fits in the inner type, which must be an unsigned integer primitive
+//! (other than `u128`). It requires the `alloc` feature.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug, Clone, Copy, PartialEq)]
+//!     #[int(public_encoding = "sqids", min_length = 8)]
+//!     u64 {
+//!         OrderId
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let order = OrderId::new(42);
+//!     let public = order.to_string();
+//!     assert_eq!(public.len(), 8);
+//!     assert_eq!(public.parse::<OrderId>().unwrap(), order);
+//!     assert!("42".parse::<OrderId>().is_err());
+//! }
+//! ```
+//!
+//! ## Check digits
+//!
+//! `#[checksum(luhn)]` and `#[checksum(iso7064_mod97)]` add check digits to account numbers and
+//! reference codes, so typos are caught when a value is parsed rather than when it's looked up.
+//! They go on `#[string]` or `#[int]` microtypes, and make the microtype validated, with
+//! [`ChecksumError`] as the error type, so `FromStr`, `Deserialize` and `FromSql` reject a value
+//! whose check digits are wrong. `generate_with_checksum(payload)` creates a value by appending the
+//! check digits to `payload`.
+//!  - `luhn` appends a single digit, as used by e.g. card numbers, and only allows ASCII digits
+//!  - `iso7064_mod97` (ISO 7064 MOD 97-10) appends two digits, as used by e.g. IBANs and LEIs, and
+//!    also allows uppercase ASCII letters
+//!
+//! Integers use their decimal digits, so the inner type must be an unsigned integer primitive, and
+//! `#[int]` can't have options, since arithmetic would break the check digits.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug, PartialEq)]
+//!     #[string]
+//!     #[checksum(luhn)]
+//!     String {
+//!         CardNumber
+//!     }
+//!
+//!     #[derive(Debug, PartialEq)]
+//!     #[int]
+//!     #[checksum(iso7064_mod97)]
+//!     u64 {
+//!         Reference
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let card: CardNumber = "79927398713".parse().unwrap();
+//!     assert_eq!(CardNumber::generate_with_checksum("7992739871").unwrap(), card);
+//!     assert!("79927398710".parse::<CardNumber>().is_err());
+//!
+//!     let reference = Reference::generate_with_checksum(12345).unwrap();
+//!     assert_eq!(reference.into_inner(), 1234520);
+//!     assert!(Reference::try_new(1234521).is_err());
+//! }
+//! ```
+//!
+//! ## Normalization
+//!
+//! `#[string(normalize = [...])]` canonicalizes a value whenever it is created, so two inputs that
+//! only differ in e.g. surrounding whitespace end up equal. The steps run in order, and can be:
+//!  - `trim` - removes leading and trailing whitespace
+//!  - `lowercase` - converts to lowercase, using [`str::to_lowercase`]
+//!  - `nfc` - converts to Unicode Normalization Form C (requires the `nfc` feature)
+//!
+//! Any other normalization can be done with `#[normalize(path::to::fn)]`, which takes the inner
+//! value and returns the normalized value. It runs after the built-in steps, and isn't limited to
+//! `#[string]` microtypes.
+//!
+//! Normalization happens in `new` (or `try_new`, before the validator), so `From`, `FromStr`,
+//! `Deserialize` and `FromSql` are all covered. To stop the value being modified afterwards,
+//! normalized microtypes have a private field and don't implement [`MicrotypeMut`] or `DerefMut`.
+//! Hand-written microtypes keep the field visibility they were declared with, and those that
+//! derive `Deserialize` themselves skip normalization when deserializing.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug, PartialEq)]
+//!     #[string(normalize = [trim, lowercase])]
+//!     String {
+//!         Username
+//!     }
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Username::from("  Alice "), Username::from("alice"));
+//!     assert_eq!(" BOB".parse::<Username>().unwrap().into_inner(), "bob");
+//! }
+//! ```
+//!
+//! ## Bounded integers
+//!
+//! `#[int(range = ...)]` rejects values outside a range, which can be any of `a..b`, `a..=b`, `a..`
+//! or `..=b`, where the bounds are const expressions. The inner type must be an integer primitive.
+//!
+//! Bounded microtypes are validated microtypes, with [`RangeError`] as the error type. On top of
+//! the usual validated implementations, they get:
+//!  - `MIN` and `MAX` associated constants
+//!  - a `const fn new_const`, which panics if the value is out of range. When used to initialize a
+//!    `const`, this means out-of-range literals are rejected at compile time
+//!  - arithmetic operators that return `Option<T>`, which is `None` if the result overflows or is
+//!    out of range. `Sum` and `Product` are implemented for `Option<T>`, and there are no `*Assign`
+//!    operators
+//!  - inherent `checked_*` methods (but not `wrapping_*`, etc.)
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug, Clone, Copy, PartialEq)]
+//!     #[int(range = 1..=100)]
+//!     u8 {
+//!         Percentage
+//!     }
+//! }
+//!
+//! const HALF: Percentage = Percentage::new_const(50);
+//!
+//! fn main() {
+//!     assert_eq!(Percentage::MAX, 100);
+//!     assert!(Percentage::try_new(0).is_err());
+//!     assert!("101".parse::<Percentage>().is_err());
+//!
+//!     assert_eq!((HALF + HALF).map(Percentage::into_inner), Some(100));
+//!     assert_eq!(HALF + 51, None);
+//! }
+//! ```
+//!
+//! ## Cross-type arithmetic
+//!
+//! `#[ops(...)]` implements arithmetic between different microtypes, e.g. for units of measure.
+//! Each entry is either:
+//!  - `A op B = C`, which implements `op` (one of `+`, `-`, `*`, `/` or `%`) for `A` and `B`, with
+//!    `C` as the output
+//!  - `A -> B * factor` or `A -> B / factor`, which implements `From<A> for B`, where the factor
+//!    is an expression of the inner type
+//!
+//! The impls are generated alongside `A`, so the attribute must be on `A` itself, or on a block
+//! that contains it. All of the types involved must implement [`Microtype`], so can't be secret or
+//! validated. An entry like `A * A = C` replaces the `A * A` impl (and its reference and `*=`
+//! variants) from `#[int]` or `#[float]`.
+//!
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     #[derive(Debug, Clone, Copy, PartialEq)]
+//!     #[float]
+//!     #[ops(
+//!         Meters / Seconds = MetersPerSecond,
+//!         Meters * Meters = SquareMeters,
+//!         Kilometers -> Meters * 1000.0,
+//!     )]
+//!     f64 {
+//!         Meters,
+//!         Kilometers,
+//!         Seconds,
+//!         MetersPerSecond,
+//!         SquareMeters,
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let distance = Meters::from(Kilometers::new(1.5));
+//!     assert_eq!(distance / Seconds::new(100.0), MetersPerSecond::new(15.0));
+//!     assert_eq!(Meters::new(3.0) * Meters::new(3.0), SquareMeters::new(9.0));
+//! }
+//! ```
+//!
 //! ## Feature flags
 //!
 //! The following feature flags are provided, to help customize the behaviour of the types creates:
 //!  - `serde` - when enabled, any type created will derive `Serialize` and `Deserialize`, and will
-//!  be `#[serde(transparent)]`
+//!    be `#[serde(transparent)]`
 //!  - `deref_impls` - some people argue that implementing `Deref` and `DerefMut` on a non-pointer container is
-//!  unidiomatic. Others prefer the ergonomics of being able to call associated functions more
-//!  easily. If `deref_impls` is enabled, microtypes will deref to their inner types
+//!    unidiomatic. Others prefer the ergonomics of being able to call associated functions more
+//!    easily. If `deref_impls` is enabled, microtypes will deref to their inner types
 //!  - `test_impls` - makes secret microtypes easier to work with in test environments by:
 //!    - making their `Debug` implmentation print their actual value instead of `"REDACTED"`
 //!    - making them derive `PartialEq`
 //!  - `secret` - enables secret microtypes, discussed below:
-//!  - `diesel` - if enabled, any attribtes of the form `#[diesel(sql_type = ...)]` will be
-//!  captured, and `FromSql` and `ToSql` implementations will be generated. Note, you will
-//!  generally also want to `#[derive(AsExpression, FromSqlRow)]`
+//!  - `diesel` (implies `std`) - if enabled, any attribtes of the form `#[diesel(sql_type = ...)]` will be
+//!    captured, and `FromSql` and `ToSql` implementations will be generated. Note, you will
+//!    generally also want to `#[derive(AsExpression, FromSqlRow)]`
+//!  - `alloc` - required by the `#[string]` type hint (e.g. for `FromStr`)
+//!  - `nfc` (implies `alloc`) - enables `#[string(normalize = [nfc])]`
+//!  - `regex` (implies `std`) - enables `#[string(pattern = "...")]`
+//!  - `uuid` (implies `std`) - enables `#[id(uuid_v4)]` and `#[id(uuid_v7)]`, and re-exports
+//!    `uuid`
+//!  - `ulid` (implies `std`) - enables `#[id(ulid)]`, and re-exports `ulid`
+//!  - `ct_eq` (implies `secret`) - implements `ConstantTimeEq` for secret microtypes, and
+//!    enables `#[secret(hash)]` (with `std`)
+//!  - `std` (implies `alloc`) - implements `std::error::Error` for the error types in this crate,
+//!    and enables `#[id(snowflake(...))]`
+//!
+//! ## `no_std`
+//!
+//! This crate is `#![no_std]`, and generated code only uses `core` (and `alloc`, via the
+//! `alloc` feature), so microtypes can be used in `#![no_std]` crates. Disable default features
+//! and re-enable the ones you need, e.g.:
+//! ```toml
+//! microtype = { version = "*", default-features = false, features = ["alloc", "deref_impls"] }
+//! ```
+//!
+//! ## Per-type overrides
+//!
+//! Since cargo features are unified across the dependency graph, enabling `serde`, `deref_impls`
+//! or `test_impls` anywhere enables it for every microtype. The `#[microtype(...)]` attribute
+//! overrides the feature flag for a single block or microtype (attributes on a microtype take
+//! precedence over attributes on its block):
+//! ```
+//! # use microtype::*;
+//! microtype! {
+//!     // keep `Deref` on ergonomic ID types
+//!     #[microtype(deref = true)]
+//!     String {
+//!         UserId
+//!     }
+//!
+//!     // but not on domain quantities
+//!     #[microtype(deref = false)]
+//!     i64 {
+//!         Cents,
+//!
+//!         #[microtype(deref = true)]
+//!         Count,
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let user_id = UserId::new("id".into());
+//!     assert_eq!(user_id.len(), 2);
+//!
+//!     let count = Count::new(1);
+//!     assert_eq!(count.pow(2), 1);
+//! }
+//! ```
+//!
+//! The keys are `serde`, `deref` and `test_impls`, and each takes a `bool`. Note that
+//! `#[microtype(serde = true)]` on a secret microtype still requires the `serde` feature, since
+//! [`secrecy`] only implements the serde traits when it is enabled
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod checksum;
+#[cfg(feature = "ct_eq")]
+mod ct_eq;
+#[cfg(all(feature = "ct_eq", feature = "std"))]
+mod secret_hash;
+#[cfg(feature = "std")]
+mod snowflake;
+#[cfg(feature = "alloc")]
+mod sqids;
 
 /* TRAIT DEFS */
 
@@ -249,9 +1053,6 @@ pub trait Microtype {
     /// Get a shared reference to the inner value
     fn inner(&self) -> &Self::Inner;
 
-    /// Get a mutable reference to the inner value
-    fn inner_mut(&mut self) -> &mut Self::Inner;
-
     /// Explicitly convert from one microtype to another.
     ///
     /// This exists as an alternative to `From`/`Into` implementations between different
@@ -259,13 +1060,21 @@ pub trait Microtype {
     fn convert<T: Microtype<Inner = Self::Inner>>(self) -> T;
 }
 
+/// A trait implemented by microtypes whose inner value can be modified in place
+///
+/// Normalized microtypes don't implement this, since the modified value might not be normalized
+pub trait MicrotypeMut: Microtype {
+    /// Get a mutable reference to the inner value
+    fn inner_mut(&mut self) -> &mut Self::Inner;
+}
+
 /// A trait implemented by secret microtypes
 ///
 /// Due to their nature, secret microtypes are more restrictive than regular microtypes:
 ///  - `inner`, `inner_mut` and `into_inner` are removed, since they can allow accidental use of
-///  the contained secret.
+///    the contained secret.
 ///  - `SecretMicrotype` requires `ExposeSecret<Self::Inner>`; to use the contained data, use
-///  `.expose_secret()`
+///    `.expose_secret()`
 ///
 ///  The wrapped type must also implement [`secrecy::Zeroize`]
 #[cfg(feature = "secret")]
@@ -281,9 +1090,333 @@ pub trait SecretMicrotype: secrecy::ExposeSecret<Self::Inner> {
     fn new(inner: Self::Inner) -> Self;
 }
 
-pub use microtype_macro::microtype;
+/// Equality that doesn't leak how much of the values match through timing
+///
+/// This is the way to compare secrets, since `==` on the exposed values can return as soon as a
+/// byte differs, so an attacker can guess a secret one byte at a time. It's implemented for every
+/// secret microtype whose inner type implements it, and for byte strings and integers (the
+/// comparison uses [`subtle`](https://docs.rs/subtle)). The lengths of the values aren't secret
+#[cfg(feature = "ct_eq")]
+pub trait ConstantTimeEq {
+    /// Whether `self` and `other` are equal, in a time that only depends on their lengths
+    fn ct_eq(&self, other: &Self) -> bool;
+}
+
+/// A trait implemented by validated microtypes
+///
+/// Validated microtypes run a validator whenever they are created, so they are more restrictive
+/// than regular microtypes:
+///  - `new` is replaced by `try_new`, which returns an error if the validator fails
+///  - `inner_mut` is removed, since it could be used to break the invariant
+pub trait ValidatedMicrotype: Sized {
+    /// The type of the wrapped value
+    type Inner;
+
+    /// The error returned when the validator fails
+    type Error;
+
+    /// Create a microtype from the inner value, if it passes the validator
+    fn try_new(inner: Self::Inner) -> Result<Self, Self::Error>;
+
+    /// Consume this microtype and return the value it contains
+    fn into_inner(self) -> Self::Inner;
+
+    /// Get a shared reference to the inner value
+    fn inner(&self) -> &Self::Inner;
+
+    /// Explicitly convert from one validated microtype to another.
+    ///
+    /// This runs the validator of the target type
+    fn try_convert<T: ValidatedMicrotype<Inner = Self::Inner>>(self) -> Result<T, T::Error>;
+}
+
+/* ERROR TYPES */
+
+/// The error returned when parsing a validated microtype from a string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError<P, V> {
+    /// The string could not be parsed into the inner type
+    Parse(P),
+    /// The string was parsed, but the inner value failed validation
+    Invalid(V),
+}
+
+impl<P: core::fmt::Display, V: core::fmt::Display> core::fmt::Display for ParseError<P, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse(e) => e.fmt(f),
+            Self::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: std::error::Error, V: std::error::Error> std::error::Error for ParseError<P, V> {}
+
+/// The error returned when a `#[int(range = ...)]` microtype is created with a value outside its
+/// range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeError<T> {
+    /// The rejected value
+    pub value: T,
+    /// The smallest allowed value
+    pub min: T,
+    /// The largest allowed value
+    pub max: T,
+}
+
+impl<T: core::fmt::Display> core::fmt::Display for RangeError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} is out of range, expected {}..={}",
+            self.value, self.min, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Debug + core::fmt::Display> std::error::Error for RangeError<T> {}
+
+/// The error returned when a `#[float(finite)]` microtype is created with NaN or infinity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NotFiniteError<T> {
+    /// The rejected value
+    pub value: T,
+}
+
+impl<T: core::fmt::Display> core::fmt::Display for NotFiniteError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} is not finite", self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Debug + core::fmt::Display> std::error::Error for NotFiniteError<T> {}
+
+/// A rule from `#[string(...)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringConstraint {
+    /// `#[string(pattern = "...")]`
+    Pattern(&'static str),
+    /// `#[string(min_len = ...)]`
+    MinLen(usize),
+    /// `#[string(max_len = ...)]`
+    MaxLen(usize),
+    /// `#[string(ascii)]`
+    Ascii,
+}
+
+/// The error returned when a `#[string(...)]` microtype is created with a value that breaks one
+/// of its constraints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringConstraintError {
+    /// The name of the microtype
+    pub microtype: &'static str,
+    /// The first constraint that was broken
+    pub constraint: StringConstraint,
+}
+
+impl core::fmt::Display for StringConstraintError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let microtype = self.microtype;
+        match self.constraint {
+            StringConstraint::Pattern(pattern) => write!(f, "{microtype} must match `{pattern}`"),
+            StringConstraint::MinLen(len) => {
+                write!(f, "{microtype} must be at least {len} characters long")
+            }
+            StringConstraint::MaxLen(len) => {
+                write!(f, "{microtype} must be at most {len} characters long")
+            }
+            StringConstraint::Ascii => write!(f, "{microtype} must only contain ASCII characters"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StringConstraintError {}
+
+/// The error returned when a `#[one_of(...)]` microtype is created with a value that isn't in its
+/// list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneOfError {
+    /// The name of the microtype
+    pub microtype: &'static str,
+    /// The allowed values
+    pub allowed: &'static [&'static str],
+}
+
+impl core::fmt::Display for OneOfError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} must be one of ", self.microtype)?;
+        for (i, value) in self.allowed.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "`{value}`")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OneOfError {}
+
+/// The error returned when a `#[prefixed_id(...)]` microtype is parsed from a string without the
+/// right prefix, or is missing the part after it. It's also returned when the prefix isn't stored,
+/// but the inner value starts with it anyway
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixError {
+    /// The name of the microtype
+    pub microtype: &'static str,
+    /// The expected prefix, without the `_` separator
+    pub prefix: &'static str,
+}
+
+impl core::fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "expected a {} like `{}_...`",
+            self.microtype, self.prefix
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrefixError {}
+
+/// The error returned when a `#[int(public_encoding = ...)]` microtype is parsed from a string
+/// that isn't a valid encoding of a single number that fits in the inner type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicEncodingError {
+    /// The name of the microtype
+    pub microtype: &'static str,
+}
+
+impl core::fmt::Display for PublicEncodingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "not a valid {}", self.microtype)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PublicEncodingError {}
+
+/// A check digit algorithm from `#[checksum(...)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// `#[checksum(luhn)]`, a single check digit, as used by e.g. card numbers
+    Luhn,
+    /// `#[checksum(iso7064_mod97)]`, two check digits, as used by e.g. IBANs and LEIs
+    Iso7064Mod97,
+}
+
+impl core::fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Luhn => f.write_str("Luhn"),
+            Self::Iso7064Mod97 => f.write_str("ISO 7064 MOD 97-10"),
+        }
+    }
+}
+
+/// Why a `#[checksum(...)]` microtype rejected a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumErrorKind {
+    /// The value has a character that the algorithm doesn't support
+    ///
+    /// Luhn only supports ASCII digits, and ISO 7064 also supports uppercase ASCII letters
+    InvalidCharacter,
+    /// The value is too short to have any digits before the check digits
+    TooShort,
+    /// The check digits are wrong
+    Mismatch,
+    /// The payload with its check digits doesn't fit in the inner type
+    Overflow,
+}
+
+/// The error returned when a `#[checksum(...)]` microtype is created with an invalid value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumError {
+    /// The name of the microtype
+    pub microtype: &'static str,
+    /// The microtype's check digit algorithm
+    pub algorithm: ChecksumAlgorithm,
+    /// Why the value was rejected
+    pub kind: ChecksumErrorKind,
+}
+
+impl core::fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let microtype = self.microtype;
+        let algorithm = self.algorithm;
+        match self.kind {
+            ChecksumErrorKind::InvalidCharacter => {
+                write!(
+                    f,
+                    "{microtype} has a character that {algorithm} doesn't support"
+                )
+            }
+            ChecksumErrorKind::TooShort => write!(f, "{microtype} is too short"),
+            ChecksumErrorKind::Mismatch => {
+                write!(f, "{microtype} has an invalid {algorithm} check digit")
+            }
+            ChecksumErrorKind::Overflow => {
+                write!(f, "{microtype} is too big to add a {algorithm} check digit")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChecksumError {}
+
+pub use microtype_macro::{microtype, Microtype};
 #[cfg(feature = "secret")]
 pub use secrecy;
+#[cfg(feature = "ulid")]
+pub use ulid;
+#[cfg(feature = "uuid")]
+pub use uuid;
+
+/// Not public API, used by generated code
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub extern crate alloc;
+    #[cfg(feature = "regex")]
+    pub use regex;
+    #[cfg(feature = "regex")]
+    pub use std::sync::OnceLock;
+    #[cfg(feature = "nfc")]
+    pub use unicode_normalization;
+
+    pub mod checksum {
+        pub use crate::checksum::{
+            str_check_digits, validate_int, validate_str, with_check_digits,
+        };
+    }
+
+    #[cfg(feature = "alloc")]
+    pub mod sqids {
+        pub use crate::sqids::{decode, encode, shuffle};
+    }
+
+    #[cfg(all(feature = "ct_eq", feature = "std"))]
+    pub use crate::secret_hash::keyed_hash;
+
+    #[cfg(feature = "diesel")]
+    pub mod diesel {
+        pub use std::io::Write;
+    }
+
+    #[cfg(feature = "std")]
+    pub mod snowflake {
+        pub use crate::snowflake::{node, sequence, timestamp, timestamp_ms, Generator};
+        pub use std::time::SystemTime;
+    }
+}
 
 #[cfg(test)]
 #[test]
@@ -296,4 +1429,39 @@ fn ui() {
     t.pass("tests/ui/pass/serde/*.rs");
     #[cfg(feature = "serde")]
     t.compile_fail("tests/ui/fail/serde/*.rs");
+    #[cfg(not(feature = "serde"))]
+    t.compile_fail("tests/ui/fail/no_serde/*.rs");
+
+    #[cfg(feature = "nfc")]
+    t.pass("tests/ui/pass/nfc/*.rs");
+    #[cfg(not(feature = "nfc"))]
+    t.compile_fail("tests/ui/fail/no_nfc/*.rs");
+
+    #[cfg(feature = "regex")]
+    t.pass("tests/ui/pass/regex/*.rs");
+    #[cfg(feature = "regex")]
+    t.compile_fail("tests/ui/fail/regex/*.rs");
+    #[cfg(not(feature = "regex"))]
+    t.compile_fail("tests/ui/fail/no_regex/*.rs");
+
+    #[cfg(feature = "uuid")]
+    t.pass("tests/ui/pass/uuid/*.rs");
+    #[cfg(feature = "uuid")]
+    t.compile_fail("tests/ui/fail/uuid/*.rs");
+    // also holds tests whose diagnostics list the `LowerHex` impls, which include `uuid`'s
+    #[cfg(not(feature = "uuid"))]
+    t.compile_fail("tests/ui/fail/no_uuid/*.rs");
+
+    #[cfg(feature = "ulid")]
+    t.pass("tests/ui/pass/ulid/*.rs");
+
+    #[cfg(feature = "ct_eq")]
+    t.pass("tests/ui/pass/ct_eq/*.rs");
+    #[cfg(feature = "ct_eq")]
+    t.compile_fail("tests/ui/fail/ct_eq/*.rs");
+    #[cfg(not(feature = "ct_eq"))]
+    t.compile_fail("tests/ui/fail/no_ct_eq/*.rs");
+
+    #[cfg(all(feature = "serde", feature = "uuid", feature = "ulid"))]
+    t.pass("tests/ui/pass/serde/ids/*.rs");
 }
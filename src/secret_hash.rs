@@ -0,0 +1,14 @@
+//! The runtime half of `#[secret(hash)]`
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::OnceLock;
+
+/// Hashes `value` with `std`'s SipHash, using a key that is chosen randomly once per process
+///
+/// Only this hash is passed on to the caller's `Hasher`, so the secret itself never goes through a
+/// hasher that might be predictable (e.g. FxHash)
+pub fn keyed_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    static KEY: OnceLock<RandomState> = OnceLock::new();
+    KEY.get_or_init(RandomState::new).hash_one(value)
+}
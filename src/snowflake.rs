@@ -0,0 +1,108 @@
+//! The runtime half of `#[id(snowflake(...))]`
+//!
+//! A snowflake is 1 unused (sign) bit, 41 bits of milliseconds since an epoch, 10 bits of node ID
+//! and 12 bits of sequence number
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NODE_BITS: u32 = 10;
+const SEQUENCE_BITS: u32 = 12;
+const TIMESTAMP_BITS: u32 = 41;
+
+const MAX_NODE: u16 = (1 << NODE_BITS) - 1;
+const MAX_SEQUENCE: u64 = (1 << SEQUENCE_BITS) - 1;
+
+/// Generates snowflakes for a single node, which are unique and increasing, even across threads
+pub struct Generator {
+    node: u64,
+    epoch: u64,
+    /// The timestamp and sequence number of the last snowflake, laid out as in the snowflake
+    last: AtomicU64,
+}
+
+impl Generator {
+    /// This is `const` so it can be used in a `static`, where an out of range node is a compile
+    /// error
+    pub const fn new(node: u16, epoch: u64) -> Self {
+        assert!(
+            node <= MAX_NODE,
+            "snowflake node IDs must be less than 1024"
+        );
+
+        Self {
+            node: node as u64,
+            epoch,
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// If the clock goes backwards, the last timestamp is reused until it catches up
+    pub fn next(&self) -> u64 {
+        let mut last = self.last.load(Ordering::Relaxed);
+
+        loop {
+            let now = self.now();
+            let last_timestamp = last >> SEQUENCE_BITS;
+
+            let next = if now > last_timestamp {
+                now << SEQUENCE_BITS
+            } else if last & MAX_SEQUENCE < MAX_SEQUENCE {
+                last + 1
+            } else {
+                // this millisecond's sequence numbers are used up
+                core::hint::spin_loop();
+                last = self.last.load(Ordering::Relaxed);
+                continue;
+            };
+
+            match self
+                .last
+                .compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => {
+                    let timestamp = next >> SEQUENCE_BITS;
+                    let sequence = next & MAX_SEQUENCE;
+                    return (timestamp << (NODE_BITS + SEQUENCE_BITS))
+                        | (self.node << SEQUENCE_BITS)
+                        | sequence;
+                }
+                Err(actual) => last = actual,
+            }
+        }
+    }
+
+    /// Milliseconds since the epoch, which is 0 if the epoch is in the future
+    fn now(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64)
+            .saturating_sub(self.epoch);
+        assert!(
+            now < 1 << TIMESTAMP_BITS,
+            "snowflake timestamps have run out, choose a later epoch"
+        );
+        now
+    }
+}
+
+/// When `id` was generated, in milliseconds since the Unix epoch
+pub const fn timestamp_ms(id: u64, epoch: u64) -> u64 {
+    (id >> (NODE_BITS + SEQUENCE_BITS)) + epoch
+}
+
+/// When `id` was generated, so that generated code doesn't need to name `std`
+pub fn timestamp(id: u64, epoch: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(timestamp_ms(id, epoch))
+}
+
+/// The node that generated `id`
+pub const fn node(id: u64) -> u16 {
+    ((id >> SEQUENCE_BITS) as u16) & MAX_NODE
+}
+
+/// The sequence number of `id` within its millisecond
+pub const fn sequence(id: u64) -> u16 {
+    (id & MAX_SEQUENCE) as u16
+}
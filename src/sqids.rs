@@ -0,0 +1,87 @@
+//! The runtime half of `#[int(public_encoding = "sqids")]`
+//!
+//! This follows the [Sqids](https://sqids.org) spec for a single number, without a blocklist, so
+//! IDs match other Sqids implementations that are configured the same way. The alphabet has
+//! already been checked by the macro
+
+use crate::__private::alloc::{string::String, vec::Vec};
+
+/// Sqids' shuffle, which is `const` so the macro can shuffle the alphabet at compile time
+pub const fn shuffle<const N: usize>(mut alphabet: [u8; N]) -> [u8; N] {
+    let mut i = 0;
+    let mut j = N - 1;
+
+    while j > 0 {
+        let r = (i * j + alphabet[i] as usize + alphabet[j] as usize) % N;
+        let swapped = alphabet[i];
+        alphabet[i] = alphabet[r];
+        alphabet[r] = swapped;
+
+        i += 1;
+        j -= 1;
+    }
+
+    alphabet
+}
+
+/// The alphabet for an ID whose first character is `alphabet[offset]`
+fn id_alphabet<const N: usize>(alphabet: &[u8; N], offset: usize) -> [u8; N] {
+    let mut chars = *alphabet;
+    chars.rotate_left(offset);
+    chars.reverse();
+    chars
+}
+
+pub fn encode<const N: usize>(number: u64, alphabet: &[u8; N], min_length: usize) -> String {
+    let offset = (alphabet[(number % N as u64) as usize] as usize + 1) % N;
+    let mut chars = id_alphabet(alphabet, offset);
+    let digits = &chars[1..];
+
+    let mut id = Vec::with_capacity(min_length.max(12));
+    let mut rest = number;
+    loop {
+        id.push(digits[(rest % digits.len() as u64) as usize]);
+        rest /= digits.len() as u64;
+        if rest == 0 {
+            break;
+        }
+    }
+    id.push(alphabet[offset]);
+    id.reverse();
+
+    if id.len() < min_length {
+        id.push(chars[0]);
+        while id.len() < min_length {
+            chars = shuffle(chars);
+            let padding = (min_length - id.len()).min(N);
+            id.extend_from_slice(&chars[..padding]);
+        }
+    }
+
+    String::from_utf8(id).expect("sqids alphabets are ASCII")
+}
+
+/// Returns `None` unless `id` is exactly what [`encode`] would return for the number, so every
+/// number has a single valid ID
+pub fn decode<const N: usize>(id: &str, alphabet: &[u8; N], min_length: usize) -> Option<u64> {
+    let (prefix, rest) = id.as_bytes().split_first()?;
+    let offset = alphabet.iter().position(|c| c == prefix)?;
+    let chars = id_alphabet(alphabet, offset);
+    let (separator, digits) = (chars[0], &chars[1..]);
+
+    let chunk = rest.split(|c| *c == separator).next()?;
+    if chunk.is_empty() {
+        return None;
+    }
+
+    let mut number: u64 = 0;
+    for c in chunk {
+        let digit = digits.iter().position(|d| d == c)?;
+        number = number
+            .checked_mul(digits.len() as u64)?
+            .checked_add(digit as u64)?;
+    }
+
+    // this rejects padding that doesn't match, extra numbers and anything else non-canonical
+    (encode(number, alphabet, min_length) == id).then_some(number)
+}
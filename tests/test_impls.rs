@@ -0,0 +1,60 @@
+//! `test_impls` only affects `cfg(test)` builds, which trybuild doesn't cover, so these live in an
+//! integration test instead
+
+#![cfg(feature = "secret")]
+
+use microtype::{microtype, SecretMicrotype};
+
+microtype! {
+    #[secret]
+    String {
+        Password
+    }
+
+    #[secret]
+    Vec<u8> {
+        SigningKey
+    }
+
+    #[secret]
+    u64 {
+        Pin
+    }
+
+    #[secret]
+    #[microtype(test_impls = false)]
+    String {
+        ApiKey
+    }
+}
+
+#[cfg(feature = "test_impls")]
+#[test]
+fn secret_debug_prints_value_in_tests() {
+    assert_eq!(
+        format!("{:?}", Password::new("hunter2".into())),
+        r#""hunter2""#
+    );
+    assert_eq!(format!("{:?}", SigningKey::new(vec![1, 2, 3])), "[1, 2, 3]");
+    assert_eq!(format!("{:?}", Pin::new(1234)), "1234");
+}
+
+#[cfg(feature = "test_impls")]
+#[test]
+fn secrets_are_partial_eq_in_tests() {
+    assert_eq!(Password::new("a".into()), Password::new("a".into()));
+    assert_ne!(SigningKey::new(vec![1]), SigningKey::new(vec![2]));
+    assert_eq!(Pin::new(1234), Pin::new(1234));
+}
+
+#[cfg(not(feature = "test_impls"))]
+#[test]
+fn secret_debug_is_redacted_without_test_impls() {
+    assert!(format!("{:?}", SigningKey::new(vec![1, 2, 3])).contains("REDACTED"));
+    assert!(format!("{:?}", Pin::new(1234)).contains("REDACTED"));
+}
+
+#[test]
+fn test_impls_can_be_disabled_per_type() {
+    assert!(format!("{:?}", ApiKey::new("key".into())).contains("REDACTED"));
+}
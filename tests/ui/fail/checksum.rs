@@ -0,0 +1,47 @@
+microtype::microtype! {
+    #[checksum(luhn)]
+    String {
+        NoTypeHint
+    }
+
+    #[int]
+    #[checksum(luhn)]
+    i64 {
+        Signed
+    }
+
+    #[int(range = 0..=99)]
+    #[checksum(luhn)]
+    u64 {
+        Bounded
+    }
+
+    #[string(max_len = 19)]
+    #[checksum(luhn)]
+    String {
+        Constrained
+    }
+
+    #[float]
+    #[checksum(luhn)]
+    f64 {
+        Float
+    }
+
+    #[checksum(crc32)]
+    #[string]
+    String {
+        Unknown
+    }
+}
+
+microtype::microtype! {
+    #[secret]
+    #[string]
+    #[checksum(iso7064_mod97)]
+    String {
+        Iban
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+microtype::microtype! {
+    f64 {
+        Meters,
+        #[ops(Meters / Seconds = MetersPerSecond)]
+        Seconds,
+        MetersPerSecond,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+fn positive(f: &f64) -> Result<(), &'static str> {
+    match *f > 0.0 {
+        true => Ok(()),
+        false => Err("not positive"),
+    }
+}
+
+microtype::microtype! {
+    f64 {
+        Seconds,
+        MetersPerSecond,
+    }
+
+    #[validate(positive)]
+    #[ops(Length / Seconds = MetersPerSecond)]
+    f64 {
+        Length
+    }
+}
+
+fn main() {}
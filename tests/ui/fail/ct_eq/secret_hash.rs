@@ -0,0 +1,14 @@
+microtype::microtype! {
+    #[derive(PartialEq)]
+    #[secret(hash)]
+    String {
+        SessionToken
+    }
+
+    #[secret(hash, hash)]
+    String {
+        ApiKey
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+microtype::microtype! {
+    #[microtype(deref = false)]
+    i64 {
+        Cents,
+
+        #[microtype(deref = true)]
+        Count,
+    }
+}
+
+fn main() {
+    use microtype::Microtype;
+    let count = Count::new(2);
+    let _ = count.pow(2);
+
+    let cents = Cents::new(2);
+    let _ = cents.pow(2);
+}
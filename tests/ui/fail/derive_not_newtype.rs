@@ -0,0 +1,10 @@
+#[derive(microtype::Microtype)]
+struct Email {
+    email: String,
+}
+
+#[derive(microtype::Microtype)]
+#[secret]
+struct Password(String);
+
+fn main() {}
@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[float(total_ord)]
+    T {
+        Measurement<T>
+    }
+}
+
+fn main() {}
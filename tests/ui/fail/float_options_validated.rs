@@ -0,0 +1,16 @@
+fn positive(f: &f64) -> Result<(), &'static str> {
+    match *f > 0.0 {
+        true => Ok(()),
+        false => Err("not positive"),
+    }
+}
+
+microtype::microtype! {
+    #[float(finite)]
+    #[validate(positive)]
+    f64 {
+        Length
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, Hash)]
+    #[float(total_ord, hash)]
+    f64 {
+        Ratio
+    }
+}
+
+fn main() {}
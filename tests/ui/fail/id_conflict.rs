@@ -0,0 +1,17 @@
+microtype::microtype! {
+    #[string]
+    #[id(uuid_v7)]
+    String {
+        UserId
+    }
+}
+
+microtype::microtype! {
+    #[prefixed_id("usr")]
+    #[id(ulid)]
+    String {
+        OtherUserId
+    }
+}
+
+fn main() {}
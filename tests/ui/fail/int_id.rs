@@ -0,0 +1,37 @@
+microtype::microtype! {
+    #[id(sequence)]
+    u64 {
+        NoHint
+    }
+
+    #[int(bit_ops)]
+    #[id(sequence)]
+    u64 {
+        WithOptions
+    }
+
+    #[int]
+    #[id(sequence)]
+    u128 {
+        TooWide
+    }
+
+    #[int]
+    #[id(snowflake(node = 1))]
+    u32 {
+        TooNarrow
+    }
+}
+
+microtype::microtype! {
+    #[int]
+    #[id(sequence)]
+    u64 {
+        Counter
+    }
+}
+
+fn main() {
+    use microtype::Microtype;
+    let _ = Counter::next() + Counter::new(1);
+}
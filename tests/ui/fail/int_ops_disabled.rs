@@ -0,0 +1,12 @@
+microtype::microtype! {
+    #[int(scalar_ops = false)]
+    u64 {
+        Count
+    }
+}
+
+fn main() {
+    use microtype::Microtype;
+    let _ = Count::new(1) + 1;
+    let _ = -Count::new(1);
+}
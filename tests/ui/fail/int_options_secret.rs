@@ -0,0 +1,9 @@
+microtype::microtype! {
+    #[secret]
+    #[int(bit_ops)]
+    u64 {
+        Pin
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+microtype::microtype! {
+    #[int(range = 1..=100)]
+    u8 {
+        Percentage
+    }
+}
+
+const TOO_BIG: Percentage = Percentage::new_const(101);
+
+fn main() {
+    let _ = TOO_BIG;
+}
@@ -0,0 +1,18 @@
+microtype::microtype! {
+    #[int(range = 10..=5)]
+    u8 {
+        Inverted
+    }
+
+    #[int(range = 5..5)]
+    i32 {
+        Empty
+    }
+
+    #[int(range = 0..0)]
+    u8 {
+        EndsAtZero
+    }
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[int(range = 1..=100)]
+    T {
+        Bounded<T>
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+fn check(_: &u8) -> Result<(), &'static str> {
+    Ok(())
+}
+
+microtype::microtype! {
+    #[int(range = 1..=10)]
+    #[validate(check)]
+    u8 {
+        Level
+    }
+}
+
+fn main() {}
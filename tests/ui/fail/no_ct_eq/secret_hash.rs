@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[secret(hash)]
+    String {
+        SessionToken
+    }
+}
+
+fn main() {}
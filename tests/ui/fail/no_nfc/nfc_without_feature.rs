@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[string(normalize = [trim, nfc])]
+    String {
+        Name
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+microtype::microtype! {
+    #[secret]
+    #[microtype(serde = true)]
+    String {
+        Password
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+microtype::microtype! {
+    #[derive(Debug)]
+    #[id(uuid_v7)]
+    Uuid {
+        OrderId
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use microtype::Microtype;
+
+microtype::microtype! {
+    #[string(normalize = [trim, lowercase])]
+    String {
+        Email
+    }
+}
+
+fn main() {
+    let mut email = Email::new(" Foo@X.com ".into());
+    email.push_str("  UPPER");
+}
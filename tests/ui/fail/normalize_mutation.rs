@@ -0,0 +1,18 @@
+use microtype::{Microtype, MicrotypeMut};
+
+mod emails {
+    microtype::microtype! {
+        #[string(normalize = [trim, lowercase])]
+        pub String {
+            Email
+        }
+    }
+}
+
+use emails::Email;
+
+fn main() {
+    let mut email = Email::new(" Foo@X.com ".into());
+    email.inner_mut().push_str("  UPPER");
+    let _ = Email(" X ".into());
+}
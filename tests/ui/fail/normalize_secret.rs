@@ -0,0 +1,13 @@
+fn lowercase(s: String) -> String {
+    s.to_lowercase()
+}
+
+microtype::microtype! {
+    #[secret]
+    #[normalize(lowercase)]
+    String {
+        Password
+    }
+}
+
+fn main() {}
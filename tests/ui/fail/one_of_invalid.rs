@@ -0,0 +1,24 @@
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("empty"),
+        false => Ok(()),
+    }
+}
+
+microtype::microtype! {
+    #[one_of("USD", "EUR")]
+    #[validate(not_empty)]
+    String {
+        Currency
+    }
+}
+
+microtype::microtype! {
+    #[int]
+    #[one_of("1", "2")]
+    u8 {
+        Digit
+    }
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[int(overflow = "wrapping")]
+    T {
+        Counter<T>
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+microtype::microtype! {
+    #[string]
+    #[prefixed_id("usr")]
+    String {
+        UserId
+    }
+}
+
+microtype::microtype! {
+    #[secret]
+    #[prefixed_id("key")]
+    String {
+        ApiKey
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+mod ids {
+    microtype::microtype! {
+        #[private]
+        pub String {
+            UserId
+        }
+
+        pub String {
+            #[private]
+            OrderId,
+            PublicId,
+        }
+    }
+}
+
+fn main() {
+    let _ = ids::PublicId("public".into());
+    let _ = ids::UserId("user".into());
+    let _ = ids::OrderId("order".into());
+    let order_id = <ids::OrderId as microtype::Microtype>::new("order".into());
+    let _ = order_id.0;
+}
@@ -0,0 +1,28 @@
+microtype::microtype! {
+    #[int(public_encoding = "sqids")]
+    i64 {
+        Signed
+    }
+
+    #[int(public_encoding = "sqids", range = 1..)]
+    u64 {
+        Bounded
+    }
+
+    #[int(alphabet = "abc")]
+    u64 {
+        NoEncoding
+    }
+
+    #[int(public_encoding = "hashids")]
+    u64 {
+        Unknown
+    }
+
+    #[int(public_encoding = "sqids", alphabet = "aab")]
+    u64 {
+        Duplicate
+    }
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[string(pattern = "^[a-z+$")]
+    String {
+        Username
+    }
+}
+
+fn main() {}
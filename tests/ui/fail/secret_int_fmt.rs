@@ -0,0 +1,13 @@
+microtype::microtype! {
+    #[secret]
+    #[int]
+    u64 {
+        Pin
+    }
+}
+
+fn main() {
+    use microtype::SecretMicrotype;
+    let pin = Pin::new(1234);
+    let _ = format!("{pin}");
+}
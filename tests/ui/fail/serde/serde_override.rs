@@ -0,0 +1,13 @@
+microtype::microtype! {
+    #[microtype(serde = false)]
+    String {
+        Email
+    }
+}
+
+fn main() {
+    use microtype::Microtype;
+    assert_serialize(Email::new("email".into()));
+}
+
+fn assert_serialize<T: serde::Serialize>(_t: T) {}
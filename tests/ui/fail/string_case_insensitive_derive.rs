@@ -0,0 +1,9 @@
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(case_insensitive)]
+    String {
+        Email
+    }
+}
+
+fn main() {}
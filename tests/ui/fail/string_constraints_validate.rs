@@ -0,0 +1,16 @@
+fn not_admin(s: &String) -> Result<(), &'static str> {
+    match s == "admin" {
+        true => Err("reserved"),
+        false => Ok(()),
+    }
+}
+
+microtype::microtype! {
+    #[string(max_len = 32)]
+    #[validate(not_admin)]
+    String {
+        Username
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+microtype::microtype! {
+    #[secret]
+    #[string(case_insensitive)]
+    String {
+        Password
+    }
+}
+
+fn main() {}
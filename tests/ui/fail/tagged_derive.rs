@@ -0,0 +1,15 @@
+microtype::microtype! {
+    #[derive(Clone)]
+    #[tagged]
+    u64 {
+        Id<T>
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[tagged]
+    String {
+        Name<T>
+    }
+}
+
+fn main() {}
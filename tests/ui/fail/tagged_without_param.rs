@@ -0,0 +1,8 @@
+microtype::microtype! {
+    #[tagged]
+    i64 {
+        Id
+    }
+}
+
+fn main() {}
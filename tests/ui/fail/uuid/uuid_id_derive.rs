@@ -0,0 +1,17 @@
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[id(uuid_v4)]
+    Uuid {
+        SessionId
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, Hash)]
+    #[id(uuid_v7)]
+    Uuid {
+        OrderId
+    }
+}
+
+fn main() {}
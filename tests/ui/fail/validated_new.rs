@@ -0,0 +1,18 @@
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("must not be empty"),
+        false => Ok(()),
+    }
+}
+
+microtype::microtype! {
+    #[validate(not_empty)]
+    String {
+        Username
+    }
+}
+
+fn main() {
+    let _ = <Username as microtype::Microtype>::new(String::new());
+    let _ = Username::from(String::new());
+}
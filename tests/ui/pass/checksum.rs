@@ -0,0 +1,111 @@
+use microtype::{
+    ChecksumAlgorithm, ChecksumError, ChecksumErrorKind, ParseError, ValidatedMicrotype,
+};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[string]
+    #[checksum(luhn)]
+    String {
+        CardNumber
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(normalize = [trim])]
+    #[checksum(iso7064_mod97)]
+    String {
+        Lei
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int]
+    #[checksum(luhn)]
+    u64 {
+        AccountNumber
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int]
+    #[checksum(iso7064_mod97)]
+    u32 {
+        Reference
+    }
+}
+
+fn main() {
+    let card: CardNumber = "79927398713".parse().unwrap();
+    assert_eq!(card.as_ref(), "79927398713");
+    assert_eq!(
+        CardNumber::generate_with_checksum("7992739871").unwrap(),
+        card
+    );
+    assert_eq!(
+        "79927398710".parse::<CardNumber>().unwrap_err(),
+        ChecksumError {
+            microtype: "CardNumber",
+            algorithm: ChecksumAlgorithm::Luhn,
+            kind: ChecksumErrorKind::Mismatch,
+        }
+    );
+    assert_eq!(
+        "79927398710".parse::<CardNumber>().unwrap_err().to_string(),
+        "CardNumber has an invalid Luhn check digit"
+    );
+    assert_eq!(
+        CardNumber::try_new("7992-7398".into()).unwrap_err().kind,
+        ChecksumErrorKind::InvalidCharacter
+    );
+    assert_eq!(
+        CardNumber::try_new("0".into()).unwrap_err().kind,
+        ChecksumErrorKind::TooShort
+    );
+    assert_eq!(
+        CardNumber::generate_with_checksum("").unwrap_err().kind,
+        ChecksumErrorKind::TooShort
+    );
+
+    // letters are allowed, and values are normalized before they're checked
+    let lei: Lei = " 529900T8BM49AURSDO55 ".parse().unwrap();
+    assert_eq!(lei.as_ref(), "529900T8BM49AURSDO55");
+    assert_eq!(Lei::generate_with_checksum("529900T8BM49AURSDO").unwrap(), lei);
+    assert!("529900T8BM49AURSDO56".parse::<Lei>().is_err());
+    assert_eq!(
+        "529900t8bm49aursdo55".parse::<Lei>().unwrap_err().kind,
+        ChecksumErrorKind::InvalidCharacter
+    );
+    assert_eq!(
+        Lei::generate_with_checksum("5299-00").unwrap_err().kind,
+        ChecksumErrorKind::InvalidCharacter
+    );
+    // the check digits are always two digits
+    assert_eq!(Lei::generate_with_checksum("30").unwrap().as_ref(), "3008");
+
+    let account = AccountNumber::generate_with_checksum(7992739871).unwrap();
+    assert_eq!(account.into_inner(), 79927398713);
+    assert_eq!("79927398713".parse::<AccountNumber>().unwrap(), account);
+    assert!(matches!(
+        "79927398714".parse::<AccountNumber>(),
+        Err(ParseError::Invalid(ChecksumError {
+            kind: ChecksumErrorKind::Mismatch,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        "seven".parse::<AccountNumber>(),
+        Err(ParseError::Parse(_))
+    ));
+    assert!(AccountNumber::try_from(79927398714).is_err());
+
+    assert_eq!(
+        Reference::generate_with_checksum(12345).unwrap().into_inner(),
+        1234520
+    );
+    assert_eq!(
+        Reference::generate_with_checksum(u32::MAX).unwrap_err(),
+        ChecksumError {
+            microtype: "Reference",
+            algorithm: ChecksumAlgorithm::Iso7064Mod97,
+            kind: ChecksumErrorKind::Overflow,
+        }
+    );
+}
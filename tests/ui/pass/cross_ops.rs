@@ -0,0 +1,59 @@
+use microtype::Microtype;
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[float]
+    #[ops(
+        Meters / Seconds = MetersPerSecond,
+        Meters * Meters = SquareMeters,
+        MetersPerSecond * Seconds = Meters,
+        Kilometers -> Meters * 1000.0,
+        Meters -> Kilometers / 1000.0,
+    )]
+    f64 {
+        Meters,
+        Kilometers,
+        Seconds,
+        MetersPerSecond,
+        SquareMeters,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int]
+    u64 {
+        #[ops(Cents + Dollars = Cents)]
+        Cents,
+        #[ops(Dollars -> Cents * 100)]
+        Dollars,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Microtype)]
+#[ops(Grams * Count = Grams)]
+struct Grams(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Microtype)]
+struct Count(u32);
+
+fn main() {
+    let distance = Meters::new(100.0);
+    let time = Seconds::new(20.0);
+
+    let speed = distance / time;
+    assert_eq!(speed, MetersPerSecond::new(5.0));
+    assert_eq!(speed * time, distance);
+    assert_eq!(distance * distance, SquareMeters::new(10_000.0));
+
+    // the rest of the `#[float]` impls are still generated
+    assert_eq!(distance + distance, Meters::new(200.0));
+    assert_eq!(distance * 2.0, Meters::new(200.0));
+
+    assert_eq!(Meters::from(Kilometers::new(1.5)), Meters::new(1500.0));
+    assert_eq!(Kilometers::from(distance), Kilometers::new(0.1));
+
+    assert_eq!(Cents::new(50) + Dollars::new(1), Cents::new(51));
+    assert_eq!(Cents::from(Dollars::new(2)), Cents::new(200));
+    assert_eq!(Cents::new(1) * Cents::new(2), Cents::new(2));
+
+    assert_eq!(Grams::new(10) * Count::new(3), Grams::new(30));
+}
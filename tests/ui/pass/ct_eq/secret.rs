@@ -0,0 +1,67 @@
+use microtype::secrecy::Secret;
+use microtype::{ConstantTimeEq, Microtype, SecretMicrotype};
+
+microtype::microtype! {
+    #[secret]
+    #[string]
+    String {
+        ApiKey
+    }
+
+    #[secret]
+    Vec<u8> {
+        SigningKey
+    }
+
+    #[secret]
+    [u8; 4] {
+        Salt
+    }
+
+    #[secret]
+    #[int]
+    u32 {
+        Pin
+    }
+
+    #[secret]
+    #[tagged]
+    String {
+        Token<T>
+    }
+}
+
+#[derive(Microtype)]
+#[secret]
+#[string]
+struct Password(Secret<String>);
+
+struct User;
+
+fn main() {
+    let key = ApiKey::new("sk_live_123".into());
+    assert!(key.ct_eq(&ApiKey::new("sk_live_123".into())));
+    assert!(!key.ct_eq(&ApiKey::new("sk_live_124".into())));
+    assert!(!key.ct_eq(&ApiKey::new("sk_live_1234".into())));
+
+    let signing_key = SigningKey::new(vec![1, 2, 3]);
+    assert!(signing_key.ct_eq(&SigningKey::new(vec![1, 2, 3])));
+    assert!(!signing_key.ct_eq(&SigningKey::new(vec![1, 2])));
+
+    assert!(Salt::new([1, 2, 3, 4]).ct_eq(&Salt::new([1, 2, 3, 4])));
+    assert!(!Salt::new([1, 2, 3, 4]).ct_eq(&Salt::new([4, 3, 2, 1])));
+
+    assert!(Pin::new(1234).ct_eq(&Pin::new(1234)));
+    assert!(!Pin::new(1234).ct_eq(&Pin::new(4321)));
+
+    let token: Token<User> = Token::new("abc".into());
+    assert!(token.ct_eq(&Token::new("abc".into())));
+
+    let password = Password::new("hunter2".into());
+    assert!(password.ct_eq(&Password::new("hunter2".into())));
+    assert!(!password.ct_eq(&Password::new("hunter3".into())));
+
+    // the inner values can be compared directly too
+    assert!("abc".ct_eq("abc"));
+    assert!(!1u64.ct_eq(&2));
+}
@@ -0,0 +1,51 @@
+use std::collections::{HashMap, HashSet};
+
+use microtype::secrecy::Secret;
+use microtype::{Microtype, SecretMicrotype};
+
+microtype::microtype! {
+    #[secret(hash)]
+    String {
+        SessionToken
+    }
+
+    #[secret(hash)]
+    #[int]
+    u64 {
+        Pin
+    }
+
+    #[secret(hash)]
+    #[tagged]
+    Vec<u8> {
+        Key<T>
+    }
+}
+
+#[derive(Microtype)]
+#[secret(hash)]
+#[string]
+struct Password(Secret<String>);
+
+struct User;
+
+fn main() {
+    let mut sessions = HashMap::new();
+    sessions.insert(SessionToken::new("abc".into()), "alice");
+    sessions.insert(SessionToken::new("def".into()), "bob");
+    assert_eq!(sessions[&SessionToken::new("abc".into())], "alice");
+    assert!(!sessions.contains_key(&SessionToken::new("ghi".into())));
+
+    assert_eq!(Pin::new(1234), Pin::new(1234));
+    assert_ne!(Pin::new(1234), Pin::new(4321));
+
+    let keys: HashSet<Key<User>> = [Key::new(vec![1, 2]), Key::new(vec![1, 2]), Key::new(vec![3])]
+        .into_iter()
+        .collect();
+    assert_eq!(keys.len(), 2);
+
+    assert!(Password::new("hunter2".into()) == Password::new("hunter2".into()));
+
+    // still redacted
+    assert!(format!("{:?}", SessionToken::new("abc".into())).contains("REDACTED"));
+}
@@ -0,0 +1,102 @@
+use microtype::secrecy::{ExposeSecret, Secret};
+use microtype::{Microtype, SecretMicrotype, ValidatedMicrotype};
+
+/// An email address
+#[derive(Debug, Clone, PartialEq, Microtype)]
+#[string]
+pub struct Email(String);
+
+impl Email {
+    pub fn domain(&self) -> Option<&str> {
+        self.0.split_once('@').map(|(_, domain)| domain)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Microtype)]
+#[int]
+struct Count(u32);
+
+#[derive(Debug, Clone, Copy, Microtype)]
+#[float(total_ord)]
+struct Celsius(f64);
+
+#[derive(Debug, Microtype)]
+struct Items<T>(Vec<T>);
+
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("empty"),
+        false => Ok(()),
+    }
+}
+
+#[derive(Debug, Microtype)]
+#[string]
+#[validate(not_empty)]
+struct Username(String);
+
+#[derive(Debug, Microtype)]
+#[one_of("USD", "EUR")]
+struct Currency(String);
+
+#[derive(Debug, Microtype)]
+#[prefixed_id("usr")]
+struct UserId(String);
+
+#[derive(Debug, Microtype)]
+#[checksum(luhn)]
+#[int]
+struct Card(u64);
+
+#[derive(Debug, Clone, Microtype)]
+#[secret]
+#[string]
+struct Password(Secret<String>);
+
+#[derive(Microtype)]
+#[secret]
+#[int]
+struct Pin(Secret<u32>);
+
+fn main() {
+    let email: Email = "user@example.com".into();
+    assert_eq!(email.domain(), Some("example.com"));
+    assert_eq!(email.to_string(), "user@example.com");
+    assert_eq!(email.len(), 16);
+
+    let count = Count::new(1) + Count::new(2);
+    assert_eq!(count.into_inner(), 3);
+
+    let temp = Celsius::new(1.5) * 2.0;
+    assert_eq!(temp, Celsius::new(3.0));
+    assert_eq!(temp.to_string(), "3");
+
+    let items = Items::new(vec![1, 2]);
+    assert_eq!(items.inner(), &[1, 2]);
+
+    let username: Username = "user".parse().unwrap();
+    assert_eq!(username.inner(), "user");
+    Username::try_new(String::new()).unwrap_err();
+
+    let currency: Currency = "EUR".parse().unwrap();
+    assert_eq!(currency.inner(), Currency::EUR);
+    Currency::try_new("GBP".into()).unwrap_err();
+
+    let user_id: UserId = "usr_123".parse().unwrap();
+    assert_eq!(user_id.inner(), "123");
+    assert_eq!(user_id.to_string(), "usr_123");
+
+    let card: Card = "79927398713".parse().unwrap();
+    assert_eq!(*card.inner(), 79927398713);
+    Card::try_new(79927398710).unwrap_err();
+    let card = Card::generate_with_checksum(7992739871).unwrap();
+    assert_eq!(card.into_inner(), 79927398713);
+
+    let password: Password = "password".parse().unwrap();
+    assert_eq!(password.clone().expose_secret(), "password");
+    assert!(format!("{password:?}").contains("REDACTED"));
+
+    let pin: Pin = "1234".parse().unwrap();
+    assert_eq!(*pin.expose_secret(), 1234);
+    assert_eq!(*Pin::new(1).expose_secret(), 1);
+}
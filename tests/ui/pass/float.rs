@@ -0,0 +1,78 @@
+use std::collections::BTreeSet;
+
+use microtype::secrecy::ExposeSecret;
+use microtype::{Microtype, ParseError, ValidatedMicrotype};
+
+fn non_negative(f: &f64) -> Result<(), &'static str> {
+    match *f >= 0.0 {
+        true => Ok(()),
+        false => Err("negative"),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    #[float]
+    f64 {
+        Celsius
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    #[float(total_ord, hash, scalar_ops = false)]
+    f32 {
+        Ratio
+    }
+
+    #[derive(Debug)]
+    #[float]
+    #[validate(non_negative)]
+    f64 {
+        Distance
+    }
+
+    #[secret]
+    #[float]
+    f64 {
+        Salary
+    }
+}
+
+fn main() {
+    let temp = Celsius::new(21.5);
+    assert_eq!(temp.to_string(), "21.5");
+    assert_eq!(format!("{temp:e}"), "2.15e1");
+    assert_eq!(format!("{temp:E}"), "2.15E1");
+    assert_eq!("21.5".parse::<Celsius>().unwrap(), temp);
+    assert!("warm".parse::<Celsius>().is_err());
+
+    let mut total = temp + temp;
+    total -= Celsius::new(3.0);
+    total *= 2.0;
+    assert_eq!(total, Celsius::new(80.0));
+    assert_eq!(&temp + &temp, Celsius::new(43.0));
+    assert_eq!(-temp, Celsius::new(-21.5));
+    assert_eq!(temp / 2.0, Celsius::new(10.75));
+    assert_eq!(Celsius::new(5.0) % 2.0, Celsius::new(1.0));
+    assert!((Celsius::new(f64::NAN) + temp).into_inner().is_nan());
+    assert_eq!([temp, temp].iter().sum::<Celsius>(), Celsius::new(43.0));
+    assert_eq!([temp, Celsius::new(2.0)].into_iter().product::<Celsius>(), Celsius::new(43.0));
+
+    let nan = Ratio::new(f32::NAN);
+    assert_eq!(nan, nan);
+    assert_ne!(Ratio::new(0.0), Ratio::new(-0.0));
+    let sorted: BTreeSet<_> = [Ratio::new(0.5), nan, Ratio::new(-1.0), Ratio::new(0.5)]
+        .into_iter()
+        .collect();
+    let sorted: Vec<_> = sorted.into_iter().map(Ratio::into_inner).collect();
+    assert_eq!(sorted[..2], [-1.0, 0.5]);
+    assert!(sorted[2].is_nan());
+    assert_eq!(Ratio::new(0.25) + Ratio::new(0.25), Ratio::new(0.5));
+
+    assert!(Distance::try_new(-1.0).is_err());
+    assert_eq!(*"1.5".parse::<Distance>().unwrap().inner(), 1.5);
+    assert!(matches!("-1".parse::<Distance>(), Err(ParseError::Invalid("negative"))));
+    assert!(matches!("x".parse::<Distance>(), Err(ParseError::Parse(_))));
+
+    let salary: Salary = "1000.5".parse().unwrap();
+    assert_eq!(*salary.expose_secret(), 1000.5);
+}
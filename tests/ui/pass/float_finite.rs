@@ -0,0 +1,52 @@
+use std::convert::TryFrom;
+
+use microtype::{NotFiniteError, ParseError, ValidatedMicrotype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[float(finite)]
+    f64 {
+        Latitude
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    #[float(finite, total_ord)]
+    f32 {
+        Score
+    }
+}
+
+fn main() {
+    assert_eq!(
+        Latitude::try_new(f64::INFINITY),
+        Err(NotFiniteError {
+            value: f64::INFINITY
+        })
+    );
+    assert!(Latitude::try_new(f64::NAN).is_err());
+    assert_eq!(
+        Latitude::try_new(f64::NEG_INFINITY).unwrap_err().to_string(),
+        "-inf is not finite"
+    );
+    assert!(Latitude::try_from(51.5).is_ok());
+    assert!(matches!("NaN".parse::<Latitude>(), Err(ParseError::Invalid(_))));
+    assert!(matches!("north".parse::<Latitude>(), Err(ParseError::Parse(_))));
+
+    let lat = Latitude::try_new(45.0).unwrap();
+    assert_eq!((lat + lat).map(Latitude::into_inner), Some(90.0));
+    assert_eq!(lat / 0.0, None);
+    assert_eq!(&lat - &lat, Latitude::try_new(0.0).ok());
+    assert_eq!(-lat, Latitude::try_new(-45.0).ok());
+    assert_eq!([lat, lat].iter().sum::<Option<Latitude>>(), Latitude::try_new(90.0).ok());
+    assert_eq!(
+        [lat, Latitude::try_new(f64::MAX).unwrap()]
+            .into_iter()
+            .product::<Option<Latitude>>(),
+        None
+    );
+
+    let low = Score::try_new(1.0).unwrap();
+    let high = Score::try_new(2.0).unwrap();
+    assert!(low < high);
+    assert_eq!(low.max(high), high);
+}
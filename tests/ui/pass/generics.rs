@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    Vec<T> {
+        Items<T>
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    &'a str {
+        UserIdRef<'a>
+    }
+
+    #[int]
+    T {
+        Counter<T>
+    }
+
+    #[secret]
+    Vec<T> {
+        SecretItems<T: Copy>
+    }
+
+    #[derive(Debug)]
+    (String, L) {
+        Tagged<L> where L: Display
+    }
+}
+
+fn main() {
+    use microtype::secrecy::ExposeSecret;
+    use microtype::{Microtype, SecretMicrotype};
+
+    let items = Items::new(vec![1, 2, 3]);
+    assert_eq!(items.len(), 3);
+    let items: Items<&str> = vec!["a"].into();
+    assert_eq!(items.into_inner(), vec!["a"]);
+
+    let owned = String::from("user");
+    let user_id = UserIdRef::new(&owned);
+    assert_eq!(*user_id, "user");
+
+    let mut counter = Counter::new(1u32) + Counter::new(2);
+    counter *= Counter::new(2);
+    assert_eq!(counter.to_string(), "6");
+
+    let secret = SecretItems::new(vec![1u8, 2]);
+    assert_eq!(secret.clone().expose_secret(), &[1, 2]);
+
+    let tagged = Tagged::new((String::from("a"), 1));
+    assert_eq!(tagged.1, 1);
+}
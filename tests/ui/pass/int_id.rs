@@ -0,0 +1,96 @@
+use std::{collections::HashSet, thread};
+
+use microtype::Microtype;
+
+const NODE: u16 = 7;
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[int]
+    #[id(sequence)]
+    u32 {
+        TicketNumber
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[int]
+    #[id(sequence)]
+    u8 {
+        SmallNumber
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[int]
+    #[id(snowflake(node = NODE))]
+    i64 {
+        EventId
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[int]
+    #[id(snowflake(node = 1023, epoch = 1_600_000_000_000))]
+    u64 {
+        MessageId
+    }
+}
+
+fn main() {
+    assert_eq!(TicketNumber::next().into_inner(), 1);
+    assert_eq!(TicketNumber::next().into_inner(), 2);
+
+    let tickets: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| (0..1000).map(|_| TicketNumber::next()).collect::<Vec<_>>()))
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    let unique: HashSet<_> = tickets.iter().copied().collect();
+    assert_eq!(unique.len(), 4000);
+
+    // `u8::MAX` is never handed out, since the next value wouldn't fit
+    for expected in 1..u8::MAX {
+        assert_eq!(SmallNumber::next().into_inner(), expected);
+    }
+    std::panic::set_hook(Box::new(|_| {}));
+    std::panic::catch_unwind(SmallNumber::next).unwrap_err();
+    let _ = std::panic::take_hook();
+
+    let first = EventId::next();
+    let events: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| (0..10_000).map(|_| EventId::next()).collect::<Vec<_>>()))
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    let unique: HashSet<_> = events.iter().copied().collect();
+    assert_eq!(unique.len(), 40_000);
+    assert!(events.iter().all(|event| *event > first));
+
+    let mut in_order: Vec<_> = (0..10_000).map(|_| EventId::next()).collect();
+    let generated = in_order.clone();
+    in_order.sort();
+    assert_eq!(in_order, generated);
+
+    assert!(*first.inner() > 0);
+    assert_eq!(first.node(), NODE);
+    assert_eq!(EventId::EPOCH_MS, 1_288_834_974_657);
+    let now = std::time::SystemTime::now();
+    let age = now.duration_since(first.timestamp()).unwrap();
+    assert!(age.as_secs() < 60);
+    assert_eq!(
+        first.timestamp_ms(),
+        first.timestamp().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as u64
+    );
+
+    let message = MessageId::next();
+    assert_eq!(message.node(), 1023);
+    assert_eq!(MessageId::EPOCH_MS, 1_600_000_000_000);
+    assert!(message.timestamp_ms() > 1_600_000_000_000);
+
+    // the parts of a known snowflake
+    let known = EventId::new((1 << 22) | (5 << 12) | 9);
+    assert_eq!(known.timestamp_ms(), EventId::EPOCH_MS + 1);
+    assert_eq!(known.node(), 5);
+    assert_eq!(known.sequence(), 9);
+
+    // `#[int]` formatting and parsing, but no arithmetic
+    assert_eq!(known.to_string(), known.inner().to_string());
+    assert_eq!(known.to_string().parse::<EventId>().unwrap(), known);
+    assert_eq!(format!("{:x}", TicketNumber::new(255)), "ff");
+}
@@ -0,0 +1,54 @@
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int]
+    i64 {
+        Price
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(bit_ops)]
+    u8 {
+        Flags
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(scalar_ops = false, ref_ops = false, iter = false, neg = false)]
+    i32 {
+        Strict
+    }
+}
+
+fn main() {
+    use microtype::Microtype;
+
+    let price = Price::new(10);
+    assert_eq!(price * 2, Price::new(20));
+    assert_eq!(price / 5, Price::new(2));
+    assert_eq!(&price + &price, Price::new(20));
+    assert_eq!(price - &Price::new(3), Price::new(7));
+    assert_eq!(-price, Price::new(-10));
+    assert_eq!(-&price, Price::new(-10));
+
+    let mut count = Price::new(0);
+    count += 1;
+    count += &price;
+    count *= 3;
+    assert_eq!(count, Price::new(33));
+
+    let prices = [Price::new(1), Price::new(2), Price::new(3)];
+    assert_eq!(prices.iter().sum::<Price>(), Price::new(6));
+    assert_eq!(prices.into_iter().product::<Price>(), Price::new(6));
+
+    let flags = Flags::new(0b1100);
+    assert_eq!(flags & Flags::new(0b0100), Flags::new(0b0100));
+    assert_eq!(flags | 0b0001, Flags::new(0b1101));
+    assert_eq!(&flags ^ &flags, Flags::new(0));
+    assert_eq!(!flags, Flags::new(0b1111_0011));
+    assert_eq!(flags >> 2u32, Flags::new(0b0011));
+    let mut shifted = flags;
+    shifted <<= 1i32;
+    assert_eq!(shifted, Flags::new(0b1_1000));
+
+    let strict = Strict::new(1) + Strict::new(2);
+    assert_eq!(strict, Strict::new(3));
+}
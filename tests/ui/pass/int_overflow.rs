@@ -0,0 +1,56 @@
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int]
+    i32 {
+        Balance
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(overflow = "saturating")]
+    u8 {
+        Level
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(overflow = "wrapping")]
+    u8 {
+        Tick
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(overflow = "checked")]
+    u64 {
+        Cents
+    }
+}
+
+fn main() {
+    use microtype::Microtype;
+
+    let max = Balance::new(i32::MAX);
+    let one = Balance::new(1);
+    assert_eq!(max.checked_add(one), None);
+    assert_eq!(max.saturating_add(one), max);
+    assert_eq!(max.wrapping_add(one), Balance::new(i32::MIN));
+    assert_eq!(max.overflowing_add(one), (Balance::new(i32::MIN), true));
+    assert_eq!(Balance::new(-3).abs(), Balance::new(3));
+    assert_eq!(Balance::new(2).pow(10), Balance::new(1024));
+    assert_eq!(Balance::new(2).checked_pow(40), None);
+
+    let mut level = Level::new(250);
+    level += 10;
+    assert_eq!(level, Level::new(255));
+    assert_eq!(Level::new(3) - Level::new(5), Level::new(0));
+    assert_eq!([Level::new(200), Level::new(100)].iter().sum::<Level>(), Level::new(255));
+
+    let mut tick = Tick::new(255);
+    tick += Tick::new(1);
+    assert_eq!(tick, Tick::new(0));
+    assert_eq!(Tick::new(16) * 16, Tick::new(0));
+
+    assert_eq!(Cents::new(1) + Cents::new(2), Some(Cents::new(3)));
+    assert_eq!(Cents::new(u64::MAX) + 1, None);
+    assert_eq!(Cents::new(1) / 0, None);
+    let total: Option<Cents> = [Cents::new(1), Cents::new(u64::MAX)].into_iter().sum();
+    assert_eq!(total, None);
+}
@@ -0,0 +1,69 @@
+use std::convert::TryFrom;
+
+use microtype::{ParseError, RangeError, ValidatedMicrotype};
+
+const LIMIT: i32 = 10;
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(range = 1..=100)]
+    u8 {
+        Percentage
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(range = -LIMIT..LIMIT, bit_ops)]
+    i32 {
+        Offset
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(range = 1..)]
+    u64 {
+        Quantity
+    }
+}
+
+const FULL: Percentage = Percentage::new_const(100);
+
+fn main() {
+    assert_eq!(Percentage::MIN, 1);
+    assert_eq!(Percentage::MAX, 100);
+    assert_eq!(Offset::MIN, -10);
+    assert_eq!(Offset::MAX, 9);
+    assert_eq!(Quantity::MAX, u64::MAX);
+
+    assert_eq!(*FULL, 100);
+    assert_eq!(
+        Percentage::try_new(0),
+        Err(RangeError {
+            value: 0,
+            min: 1,
+            max: 100
+        })
+    );
+    assert_eq!(
+        Percentage::try_new(101).unwrap_err().to_string(),
+        "101 is out of range, expected 1..=100"
+    );
+    assert!(Percentage::try_from(50).is_ok());
+
+    assert_eq!("50".parse::<Percentage>().unwrap(), Percentage::try_new(50).unwrap());
+    assert!(matches!("0".parse::<Percentage>(), Err(ParseError::Invalid(_))));
+    assert!(matches!("x".parse::<Percentage>(), Err(ParseError::Parse(_))));
+
+    let half = Percentage::try_new(50).unwrap();
+    assert_eq!(half + half, Some(FULL));
+    assert_eq!(half + FULL, None);
+    assert_eq!(half - 50, None);
+    assert_eq!(half.checked_mul(Percentage::try_new(2).unwrap()), Some(FULL));
+    assert_eq!(Percentage::try_new(200 / 2).unwrap().checked_add(FULL), None);
+    assert_eq!([half, half].iter().sum::<Option<Percentage>>(), Some(FULL));
+
+    let offset = Offset::try_new(9).unwrap();
+    assert_eq!(offset + 1, None);
+    assert_eq!(-offset, Offset::try_new(-9).ok());
+    assert_eq!(!offset, Offset::try_new(-10).ok());
+    assert_eq!(offset | 16, None);
+    assert_eq!(offset >> 1, Offset::try_new(4).ok());
+}
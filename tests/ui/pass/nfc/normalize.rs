@@ -0,0 +1,16 @@
+use microtype::Microtype;
+
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[string(normalize = [nfc])]
+    String {
+        Name
+    }
+}
+
+fn main() {
+    // "e" followed by a combining acute accent is composed into "é"
+    let decomposed = Name::from("Re\u{301}sume\u{301}");
+    assert_eq!(decomposed, Name::from("R\u{e9}sum\u{e9}"));
+    assert_eq!(decomposed.into_inner().chars().count(), 6);
+}
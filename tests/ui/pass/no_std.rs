@@ -0,0 +1,76 @@
+#![no_std]
+
+// only needed to link the test binary, generated code must not use it
+extern crate std as _std;
+
+extern crate alloc;
+
+use alloc::string::String;
+use microtype::secrecy::ExposeSecret;
+use microtype::{Microtype, ValidatedMicrotype};
+
+fn positive(i: &i64) -> Result<(), &'static str> {
+    match *i > 0 {
+        true => Ok(()),
+        false => Err("not positive"),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[string]
+    String {
+        Email
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int]
+    i64 {
+        Cents
+    }
+
+    #[derive(Debug)]
+    #[int]
+    #[validate(positive)]
+    i64 {
+        Quantity
+    }
+
+    #[secret]
+    #[string]
+    String {
+        Password
+    }
+
+    #[tagged]
+    u64 {
+        Id<T>
+    }
+
+    #[int]
+    #[id(snowflake(node = 1))]
+    u64 {
+        EventId
+    }
+}
+
+fn main() {
+    let email: Email = "email".parse().unwrap();
+    assert_eq!(email, Email::from("email"));
+    assert_eq!(email.as_ref(), "email");
+
+    let cents = Cents::new(1) + Cents::new(2);
+    assert_eq!(cents.into_inner(), 3);
+
+    assert!(Quantity::try_new(0).is_err());
+    assert_eq!(*"1".parse::<Quantity>().unwrap().inner(), 1);
+
+    let password: Password = "password".parse().unwrap();
+    assert_eq!(password.clone().expose_secret(), "password");
+
+    assert_eq!(Id::<()>::new(1), Id::new(1));
+
+    let event_id = EventId::next();
+    assert_eq!(event_id.node(), 1);
+    assert!(event_id.timestamp() > _std::time::UNIX_EPOCH);
+}
@@ -1,4 +1,4 @@
-use microtype::Microtype;
+use microtype::{Microtype, MicrotypeMut};
 
 microtype_macro::microtype! {
     #[derive(Clone)]
@@ -0,0 +1,58 @@
+use microtype::{Microtype, ValidatedMicrotype};
+
+fn canonical_tag(s: String) -> String {
+    s.replace(' ', "-")
+}
+
+fn has_at(s: &String) -> Result<(), &'static str> {
+    match s.contains('@') {
+        true => Ok(()),
+        false => Err("missing @"),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(normalize = [trim, lowercase])]
+    String {
+        Username
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(normalize = [trim])]
+    #[normalize(canonical_tag)]
+    String {
+        Tag
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(normalize = [trim, lowercase])]
+    #[validate(has_at)]
+    String {
+        Email
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[normalize(i64::abs)]
+    i64 {
+        Distance
+    }
+}
+
+fn main() {
+    assert_eq!(Username::new("  Alice\t".into()).into_inner(), "alice");
+    assert_eq!(Username::from("BOB "), Username::from("bob"));
+    assert_eq!("  Carol".parse::<Username>().unwrap().into_inner(), "carol");
+    assert_eq!(Username::from(String::from("\nDave")).into_inner(), "dave");
+    assert_eq!(Username::from("   ").into_inner(), "");
+
+    // the built-in steps run before the custom function
+    assert_eq!(Tag::from("  rust lang ").into_inner(), "rust-lang");
+
+    // values are normalized before they're validated
+    let email = Email::try_new("  User@Example.COM ".into()).unwrap();
+    assert_eq!(email.into_inner(), "user@example.com");
+    assert_eq!("  nope ".parse::<Email>().unwrap_err(), "missing @");
+
+    assert_eq!(Distance::new(-3), Distance::new(3));
+}
@@ -0,0 +1,53 @@
+use microtype::{OneOfError, ValidatedMicrotype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[one_of("USD", "EUR", "GBP")]
+    String {
+        Currency
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(normalize = [trim, lowercase])]
+    #[one_of("en-gb", "en-us")]
+    String {
+        Locale
+    }
+}
+
+fn symbol(currency: &Currency) -> &'static str {
+    match currency.as_ref() {
+        Currency::USD => "$",
+        Currency::EUR => "€",
+        Currency::GBP => "£",
+        _ => unreachable!(),
+    }
+}
+
+fn main() {
+    assert_eq!(Currency::ALL, ["USD", "EUR", "GBP"]);
+    assert_eq!(Currency::USD, "USD");
+
+    let currency: Currency = "EUR".parse().unwrap();
+    assert_eq!(symbol(&currency), "€");
+    assert_eq!(currency.to_string(), "EUR");
+    assert_eq!(Currency::try_from(Currency::GBP).unwrap().into_inner(), "GBP");
+
+    let error = Currency::try_new("usd".into()).unwrap_err();
+    assert_eq!(
+        error,
+        OneOfError {
+            microtype: "Currency",
+            allowed: Currency::ALL,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Currency must be one of `USD`, `EUR`, `GBP`"
+    );
+
+    // values are normalized before they're checked
+    assert_eq!(Locale::EN_GB, "en-gb");
+    assert_eq!(Locale::try_from(" EN-GB ").unwrap().into_inner(), Locale::EN_GB);
+    assert!(Locale::try_from("fr-fr").is_err());
+}
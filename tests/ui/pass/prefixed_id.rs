@@ -0,0 +1,62 @@
+use microtype::{PrefixError, ValidatedMicrotype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[prefixed_id("usr")]
+    String {
+        UserId
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[prefixed_id("ord", stored)]
+    String {
+        OrderId
+    }
+}
+
+fn main() {
+    assert_eq!(UserId::PREFIX, "usr");
+
+    // the prefix is stripped from the inner value
+    let user: UserId = "usr_01H8XGJWBWBAQ4Z4E2ZT7GQKQP".parse().unwrap();
+    assert_eq!(user.inner(), "01H8XGJWBWBAQ4Z4E2ZT7GQKQP");
+    assert_eq!(user.to_string(), "usr_01H8XGJWBWBAQ4Z4E2ZT7GQKQP");
+    assert_eq!(
+        UserId::try_new("01H8".into()).unwrap().to_string(),
+        "usr_01H8"
+    );
+
+    // the prefix is kept in the inner value
+    let order: OrderId = "ord_42".parse().unwrap();
+    assert_eq!(order.inner(), "ord_42");
+    assert_eq!(order.to_string(), "ord_42");
+    assert_eq!(order.as_ref(), "ord_42");
+    assert!(OrderId::try_new("42".into()).is_err());
+
+    let error = "ord_42".parse::<UserId>().unwrap_err();
+    assert_eq!(
+        error,
+        PrefixError {
+            microtype: "UserId",
+            prefix: "usr",
+        }
+    );
+    assert_eq!(error.to_string(), "expected a UserId like `usr_...`");
+
+    assert!("usr_".parse::<UserId>().is_err());
+    assert!("usr".parse::<UserId>().is_err());
+    assert!("ord_".parse::<OrderId>().is_err());
+    assert!(UserId::try_new(String::new()).is_err());
+
+    // an unstored prefix can't be passed to `try_new`, otherwise it would be displayed twice
+    assert!(UserId::try_new("usr_abc".into()).is_err());
+    assert!("usr_usr_abc".parse::<UserId>().is_err());
+
+    // `try_new` -> `Display` -> `FromStr` gives back the same value
+    for inner in ["abc", "usr", "usrabc", "ord_abc"] {
+        let user = UserId::try_new(inner.into()).unwrap();
+        assert_eq!(user.to_string().parse::<UserId>().unwrap(), user);
+    }
+    let order = OrderId::try_new("ord_42".into()).unwrap();
+    assert_eq!(order.to_string().parse::<OrderId>().unwrap(), order);
+}
@@ -0,0 +1,27 @@
+mod ids {
+    microtype::microtype! {
+        #[private]
+        pub String {
+            UserId
+        }
+    }
+
+    pub fn make_user_id() -> UserId {
+        // still constructible in the defining module
+        UserId("user".into())
+    }
+}
+
+use microtype::{Microtype, MicrotypeMut};
+
+fn main() {
+    let mut user_id = ids::UserId::new("hello".into());
+    assert_eq!(user_id.inner(), "hello");
+    user_id.inner_mut().push('!');
+    assert_eq!(user_id.into_inner(), "hello!");
+
+    let user_id: ids::UserId = String::from("from").into();
+    assert_eq!(user_id.inner(), "from");
+
+    assert_eq!(ids::make_user_id().into_inner(), "user");
+}
@@ -0,0 +1,65 @@
+use microtype::{Microtype, PublicEncodingError};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(public_encoding = "sqids")]
+    u64 {
+        OrderId
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(public_encoding = "sqids", alphabet = "k3G7QAe51FCsPW92uEOyq4Bg6Sp8YzVTmnU0liwDdHXLajZrfxNhobJIRcMvKt", min_length = 10)]
+    u32 {
+        InvoiceId
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(public_encoding = "sqids", alphabet = "abc")]
+    u8 {
+        Tiny
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[int(public_encoding = "sqids", alphabet = "abc")]
+    u64 {
+        Wide
+    }
+}
+
+fn main() {
+    // the test vectors from the Sqids spec
+    let ids = ["bM", "Uk", "gb", "Ef", "Vq", "uw", "OI", "AX", "p6", "nJ"];
+    for (number, id) in ids.into_iter().enumerate() {
+        assert_eq!(OrderId::new(number as u64).to_string(), id);
+        assert_eq!(id.parse::<OrderId>().unwrap(), OrderId::new(number as u64));
+    }
+
+    for number in [0, 1, 61, 62, 1_000_000, u64::MAX] {
+        let id = OrderId::new(number);
+        assert_eq!(id.to_string().parse::<OrderId>().unwrap(), id);
+    }
+
+    let invoice = InvoiceId::new(42);
+    let s = invoice.to_string();
+    assert_eq!(s.len(), 10);
+    assert_eq!(s.parse::<InvoiceId>().unwrap(), invoice);
+    assert_ne!(s, OrderId::new(42).to_string());
+
+    // `[1, 2, 3]`, IDs with the wrong padding, and numbers that are too big
+    let error = PublicEncodingError {
+        microtype: "OrderId",
+    };
+    assert_eq!("86Rf07".parse::<OrderId>(), Err(error));
+    assert_eq!("".parse::<OrderId>(), Err(error));
+    assert_eq!("bM!".parse::<OrderId>(), Err(error));
+    assert_eq!(error.to_string(), "not a valid OrderId");
+    let unpadded = OrderId::new(42).to_string();
+    unpadded.parse::<InvoiceId>().unwrap_err();
+    let max = Wide::new(u8::MAX.into()).to_string();
+    assert_eq!(max.parse::<Tiny>().unwrap(), Tiny::new(u8::MAX));
+    let too_big = Wide::new(u64::from(u8::MAX) + 1).to_string();
+    too_big.parse::<Tiny>().unwrap_err();
+
+    // padding and alignment apply to the encoded form
+    assert_eq!(format!("{:>4}", OrderId::new(0)), "  bM");
+}
@@ -0,0 +1,32 @@
+use microtype::{StringConstraint, ValidatedMicrotype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(pattern = "^[a-z0-9_]+$", min_len = 3, max_len = 32)]
+    String {
+        Username
+    }
+
+    #[derive(Debug)]
+    #[string(pattern = "[a-z]{3,32}")]
+    String {
+        Handle
+    }
+}
+
+fn main() {
+    assert!(Username::try_from("alice_99").is_ok());
+
+    let error = "Alice".parse::<Username>().unwrap_err();
+    assert_eq!(error.constraint, StringConstraint::Pattern("^[a-z0-9_]+$"));
+    assert_eq!(error.to_string(), "Username must match `^[a-z0-9_]+$`");
+
+    // the length is checked before the pattern
+    let error = Username::try_new("a!".into()).unwrap_err();
+    assert_eq!(error.constraint, StringConstraint::MinLen(3));
+
+    // the whole value has to match, even without `^` and `$`
+    assert!("abc".parse::<Handle>().is_ok());
+    assert!("!!abc!!".parse::<Handle>().is_err());
+    assert!("abc!".parse::<Handle>().is_err());
+}
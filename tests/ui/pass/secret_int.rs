@@ -0,0 +1,32 @@
+microtype::microtype! {
+    #[secret]
+    #[int]
+    u64 {
+        Pin
+    }
+
+    #[secret]
+    #[int]
+    ::core::primitive::i32 {
+        Cvv
+    }
+}
+
+fn main() {
+    use microtype::secrecy::ExposeSecret;
+    use microtype::SecretMicrotype;
+
+    let pin = Pin::new(1234);
+    assert_eq!(*pin.expose_secret(), 1234);
+
+    let pin: Pin = "4321".parse().unwrap();
+    assert_eq!(*pin.expose_secret(), 4321);
+    assert!("asdf".parse::<Pin>().is_err());
+
+    let cvv: Cvv = "-123".parse().unwrap();
+    assert_eq!(*cvv.expose_secret(), -123);
+
+    let debug = format!("{:?}", pin);
+    assert!(debug.contains("REDACTED"));
+    assert!(!debug.contains("4321"));
+}
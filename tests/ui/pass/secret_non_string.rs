@@ -0,0 +1,24 @@
+microtype::microtype! {
+    #[secret]
+    Vec<u8> {
+        SigningKey
+    }
+
+    #[secret]
+    u64 {
+        Pin
+    }
+}
+
+fn main() {
+    use microtype::secrecy::ExposeSecret;
+    use microtype::SecretMicrotype;
+
+    let key = SigningKey::new(vec![1, 2, 3]);
+    assert_eq!(key.clone().expose_secret(), &[1, 2, 3]);
+    assert!(format!("{key:?}").contains("REDACTED"));
+
+    let pin = Pin::new(1234);
+    assert_eq!(*pin.clone().expose_secret(), 1234);
+    assert!(format!("{pin:?}").contains("REDACTED"));
+}
@@ -0,0 +1,28 @@
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[int]
+    #[checksum(luhn)]
+    u64 {
+        AccountNumber
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[string]
+    #[checksum(iso7064_mod97)]
+    String {
+        Lei
+    }
+}
+
+fn main() {
+    let account: AccountNumber = serde_json::from_str("79927398713").unwrap();
+    assert_eq!(serde_json::to_string(&account).unwrap(), "79927398713");
+    assert!(serde_json::from_str::<AccountNumber>("79927398710").is_err());
+
+    let lei: Lei = serde_json::from_str(r#""529900T8BM49AURSDO55""#).unwrap();
+    assert_eq!(lei, Lei::generate_with_checksum("529900T8BM49AURSDO").unwrap());
+    let error = serde_json::from_str::<Lei>(r#""529900T8BM49AURSDO54""#).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("Lei has an invalid ISO 7064 MOD 97-10 check digit"));
+}
@@ -0,0 +1,27 @@
+use microtype::Microtype;
+
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("empty"),
+        false => Ok(()),
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, Microtype)]
+#[serde(transparent)]
+struct Email(String);
+
+// `Deserialize` is generated, so that it can't bypass the validator
+#[derive(Debug, serde::Serialize, Microtype)]
+#[serde(transparent)]
+#[validate(not_empty)]
+struct Username(String);
+
+fn main() {
+    let email: Email = serde_json::from_str("\"email\"").unwrap();
+    assert_eq!(email, Email::new("email".into()));
+
+    let username: Username = serde_json::from_str("\"user\"").unwrap();
+    assert_eq!(serde_json::to_string(&username).unwrap(), "\"user\"");
+    serde_json::from_str::<Username>("\"\"").unwrap_err();
+}
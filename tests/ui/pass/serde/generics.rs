@@ -0,0 +1,47 @@
+fn non_empty<T>(items: &Vec<T>) -> Result<(), &'static str> {
+    match items.is_empty() {
+        true => Err("empty"),
+        false => Ok(()),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    Vec<T> {
+        Items<T>
+    }
+
+    #[derive(Debug)]
+    #[validate(non_empty)]
+    Vec<T> {
+        NonEmpty<T>
+    }
+
+    #[secret(serialize)]
+    Vec<T> {
+        SecretItems<T>
+    }
+
+    #[derive(Debug)]
+    &'a str {
+        UserIdRef<'a>
+    }
+}
+
+fn main() {
+    use microtype::{Microtype, SecretMicrotype};
+
+    let items: Items<u32> = serde_json::from_str("[1, 2]").unwrap();
+    assert_eq!(serde_json::to_string(&items).unwrap(), "[1,2]");
+
+    let non_empty: NonEmpty<u32> = serde_json::from_str("[1]").unwrap();
+    assert_eq!(serde_json::to_string(&non_empty).unwrap(), "[1]");
+    serde_json::from_str::<NonEmpty<u32>>("[]").unwrap_err();
+
+    let secret: SecretItems<u8> = serde_json::from_str("[1]").unwrap();
+    assert_eq!(serde_json::to_string(&secret).unwrap(), "[1]");
+    let _ = SecretItems::new(vec![1u8]);
+
+    let user_id: UserIdRef = serde_json::from_str("\"user\"").unwrap();
+    assert_eq!(user_id.into_inner(), "user");
+}
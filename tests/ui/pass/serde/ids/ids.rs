@@ -0,0 +1,30 @@
+use microtype::{ulid::Ulid, uuid::Uuid, Microtype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy)]
+    #[id(uuid_v7)]
+    Uuid {
+        OrderId
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    #[id(ulid)]
+    Ulid {
+        EventId
+    }
+}
+
+fn main() {
+    let order = OrderId::generate();
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(json, format!(r#""{}""#, order.inner()));
+    assert_eq!(serde_json::from_str::<OrderId>(&json).unwrap(), order);
+
+    let event = EventId::generate();
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(json, format!(r#""{}""#, event.inner()));
+    assert_eq!(serde_json::from_str::<EventId>(&json).unwrap(), event);
+
+    serde_json::from_str::<OrderId>(r#""not a uuid""#).unwrap_err();
+    serde_json::from_str::<EventId>("123").unwrap_err();
+}
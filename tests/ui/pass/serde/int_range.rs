@@ -0,0 +1,18 @@
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[int(range = 1..=100)]
+    u8 {
+        Percentage
+    }
+}
+
+fn main() {
+    use microtype::ValidatedMicrotype;
+
+    let percentage: Percentage = serde_json::from_str("50").unwrap();
+    assert_eq!(percentage, Percentage::try_new(50).unwrap());
+    assert_eq!(serde_json::to_string(&percentage).unwrap(), "50");
+
+    let error = serde_json::from_str::<Percentage>("101").unwrap_err();
+    assert_eq!(error.to_string(), "101 is out of range, expected 1..=100");
+}
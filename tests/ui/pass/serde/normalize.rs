@@ -0,0 +1,13 @@
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[string(normalize = [trim, lowercase])]
+    String {
+        Username
+    }
+}
+
+fn main() {
+    let username: Username = serde_json::from_str(r#"" Alice ""#).unwrap();
+    assert_eq!(username, Username::from("alice"));
+    assert_eq!(serde_json::to_string(&username).unwrap(), r#""alice""#);
+}
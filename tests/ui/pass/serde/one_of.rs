@@ -0,0 +1,21 @@
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[one_of("USD", "EUR", "GBP")]
+    String {
+        Currency
+    }
+}
+
+fn main() {
+    use microtype::ValidatedMicrotype;
+
+    let currency: Currency = serde_json::from_str(r#""GBP""#).unwrap();
+    assert_eq!(currency.inner(), Currency::GBP);
+    assert_eq!(serde_json::to_string(&currency).unwrap(), r#""GBP""#);
+
+    let error = serde_json::from_str::<Currency>(r#""JPY""#).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Currency must be one of `USD`, `EUR`, `GBP`"
+    );
+}
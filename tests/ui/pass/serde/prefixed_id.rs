@@ -0,0 +1,29 @@
+use microtype::ValidatedMicrotype;
+
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[prefixed_id("usr")]
+    String {
+        UserId
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[prefixed_id("ord", stored)]
+    String {
+        OrderId
+    }
+}
+
+fn main() {
+    let user: UserId = serde_json::from_str(r#""usr_123""#).unwrap();
+    assert_eq!(user.inner(), "123");
+    assert_eq!(serde_json::to_string(&user).unwrap(), r#""usr_123""#);
+
+    let order: OrderId = serde_json::from_str(r#""ord_456""#).unwrap();
+    assert_eq!(order.inner(), "ord_456");
+    assert_eq!(serde_json::to_string(&order).unwrap(), r#""ord_456""#);
+
+    let error = serde_json::from_str::<UserId>(r#""ord_456""#).unwrap_err();
+    assert!(error.to_string().contains("expected a UserId like `usr_...`"));
+    serde_json::from_str::<UserId>(r#""123""#).unwrap_err();
+}
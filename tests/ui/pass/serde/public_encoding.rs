@@ -0,0 +1,19 @@
+use microtype::Microtype;
+
+microtype::microtype! {
+    #[derive(Debug, PartialEq)]
+    #[int(public_encoding = "sqids")]
+    u64 {
+        OrderId
+    }
+}
+
+fn main() {
+    let order = OrderId::new(1);
+    assert_eq!(serde_json::to_string(&order).unwrap(), r#""Uk""#);
+    assert_eq!(serde_json::from_str::<OrderId>(r#""Uk""#).unwrap(), order);
+
+    let error = serde_json::from_str::<OrderId>(r#""86Rf07""#).unwrap_err();
+    assert!(error.to_string().contains("not a valid OrderId"));
+    serde_json::from_str::<OrderId>("1").unwrap_err();
+}
@@ -0,0 +1,24 @@
+microtype::microtype! {
+    #[secret]
+    #[int]
+    u64 {
+        Pin
+    }
+
+    #[secret(serialize)]
+    #[int]
+    u64 {
+        Otp
+    }
+}
+
+fn main() {
+    use microtype::secrecy::ExposeSecret;
+
+    let pin: Pin = serde_json::from_str("1234").unwrap();
+    assert_eq!(*pin.expose_secret(), 1234);
+    assert!(serde_json::from_str::<Pin>(r#""1234""#).is_err());
+
+    let otp: Otp = serde_json::from_str("123456").unwrap();
+    assert_eq!(serde_json::to_string(&otp).unwrap(), "123456");
+}
@@ -0,0 +1,27 @@
+microtype::microtype! {
+    #[tagged]
+    i64 {
+        Id<T>
+    }
+
+    #[secret(serialize)]
+    #[tagged]
+    String {
+        Token<T>
+    }
+}
+
+struct User;
+
+fn main() {
+    use microtype::secrecy::ExposeSecret;
+    use microtype::Microtype;
+
+    let id: Id<User> = serde_json::from_str("123").unwrap();
+    assert_eq!(id, Id::new(123));
+    assert_eq!(serde_json::to_string(&id).unwrap(), "123");
+
+    let token: Token<User> = serde_json::from_str("\"token\"").unwrap();
+    assert_eq!(token.expose_secret(), "token");
+    assert_eq!(serde_json::to_string(&token).unwrap(), "\"token\"");
+}
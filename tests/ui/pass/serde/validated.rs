@@ -0,0 +1,25 @@
+use microtype::ValidatedMicrotype;
+
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("must not be empty"),
+        false => Ok(()),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug)]
+    #[validate(not_empty)]
+    String {
+        Username
+    }
+}
+
+fn main() {
+    let username: Username = serde_json::from_str(r#""hello""#).unwrap();
+    assert_eq!(username.inner(), "hello");
+    assert_eq!(serde_json::to_string(&username).unwrap(), r#""hello""#);
+
+    let err = serde_json::from_str::<Username>(r#""""#).unwrap_err();
+    assert!(err.to_string().contains("must not be empty"));
+}
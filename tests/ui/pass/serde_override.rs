@@ -0,0 +1,33 @@
+// these types are serializable even when the `serde` feature is disabled
+
+use microtype::Microtype;
+
+microtype::microtype! {
+    #[microtype(serde = true)]
+    String {
+        Email
+    }
+
+    #[microtype(serde = true)]
+    #[validate(not_empty)]
+    String {
+        Username
+    }
+}
+
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("must not be empty"),
+        false => Ok(()),
+    }
+}
+
+fn main() {
+    let email = Email::new("email".into());
+    assert_eq!(serde_json::to_string(&email).unwrap(), r#""email""#);
+
+    let email: Email = serde_json::from_str(r#""email""#).unwrap();
+    assert_eq!(email.inner(), "email");
+
+    assert!(serde_json::from_str::<Username>(r#""""#).is_err());
+}
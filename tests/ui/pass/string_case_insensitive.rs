@@ -0,0 +1,57 @@
+use std::collections::{BTreeSet, HashSet};
+
+use microtype::{Microtype, ValidatedMicrotype};
+
+fn has_at(s: &String) -> Result<(), &'static str> {
+    match s.contains('@') {
+        true => Ok(()),
+        false => Err("missing @"),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, Clone)]
+    #[string(case_insensitive)]
+    String {
+        HeaderName
+    }
+
+    #[derive(Debug, Clone)]
+    #[string(case_insensitive = "unicode")]
+    String {
+        Username
+    }
+
+    #[derive(Debug, Clone)]
+    #[string(case_insensitive)]
+    #[validate(has_at)]
+    String {
+        Email
+    }
+}
+
+fn main() {
+    let header = HeaderName::from("Content-Type");
+    assert_eq!(header, HeaderName::from("content-type"));
+    assert_ne!(header, HeaderName::from("content-length"));
+    assert_eq!(header.to_string(), "Content-Type");
+    assert!(HeaderName::from("a") < HeaderName::from("B"));
+    // only ASCII letters are folded
+    assert_ne!(HeaderName::from("Ä"), HeaderName::from("ä"));
+
+    let set: HashSet<_> = ["Accept", "ACCEPT", "accept", "Host"]
+        .into_iter()
+        .map(HeaderName::from)
+        .collect();
+    assert_eq!(set.len(), 2);
+
+    assert_eq!(Username::from("Ärger"), Username::from("äRGER"));
+    assert_eq!(Username::from("straße"), Username::from("STRASSE"));
+    let sorted: BTreeSet<_> = ["b", "C", "A"].into_iter().map(Username::from).collect();
+    let sorted: Vec<_> = sorted.into_iter().map(Username::into_inner).collect();
+    assert_eq!(sorted, ["A", "b", "C"]);
+
+    let email = Email::try_new("User@Example.com".to_string()).unwrap();
+    assert_eq!(email, "user@example.com".parse().unwrap());
+    assert_eq!(email.to_string(), "User@Example.com");
+}
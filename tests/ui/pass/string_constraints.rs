@@ -0,0 +1,44 @@
+use microtype::{StringConstraint, StringConstraintError, ValidatedMicrotype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(min_len = 3, max_len = 8, ascii)]
+    String {
+        Handle
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[string(normalize = [trim], max_len = 4)]
+    String {
+        Code
+    }
+}
+
+fn main() {
+    let handle = Handle::try_new("alice".into()).unwrap();
+    assert_eq!(handle.inner(), "alice");
+    assert_eq!("bob".parse::<Handle>().unwrap().into_inner(), "bob");
+
+    let error = Handle::try_from("al").unwrap_err();
+    assert_eq!(
+        error,
+        StringConstraintError {
+            microtype: "Handle",
+            constraint: StringConstraint::MinLen(3),
+        }
+    );
+    assert_eq!(error.to_string(), "Handle must be at least 3 characters long");
+
+    let error = "abcdefghi".parse::<Handle>().unwrap_err();
+    assert_eq!(error.constraint, StringConstraint::MaxLen(8));
+    assert_eq!(error.to_string(), "Handle must be at most 8 characters long");
+
+    let error = Handle::try_from("älice").unwrap_err();
+    assert_eq!(error.constraint, StringConstraint::Ascii);
+    assert_eq!(error.to_string(), "Handle must only contain ASCII characters");
+
+    // lengths are counted after normalization, in characters rather than bytes
+    assert!(Code::try_from("  ab12  ").is_ok());
+    assert!(Code::try_from("ÄÖÜß").is_ok());
+    assert!(Code::try_from("abcde").is_err());
+}
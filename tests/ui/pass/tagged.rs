@@ -0,0 +1,55 @@
+use std::collections::{BTreeSet, HashSet};
+
+microtype::microtype! {
+    #[tagged]
+    i64 {
+        Id<T>
+    }
+
+    #[tagged]
+    String {
+        Name<T>
+    }
+
+    #[secret]
+    #[tagged]
+    String {
+        Token<T>
+    }
+}
+
+// deliberately doesn't implement anything
+struct User;
+struct Order;
+
+fn main() {
+    use microtype::secrecy::ExposeSecret;
+    use microtype::{Microtype, SecretMicrotype};
+
+    let user_id: Id<User> = Id::new(1);
+    let copy = user_id;
+    assert_eq!(user_id, copy);
+    assert!(Id::<User>::new(1) < Id::new(2));
+    assert_eq!(format!("{user_id:?}"), "Id(1)");
+
+    let set: HashSet<Id<User>> = [Id::new(1), Id::new(1), Id::new(2)].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    let set: BTreeSet<Id<Order>> = [Id::new(3), Id::new(1)].into_iter().collect();
+    assert_eq!(set.into_iter().next(), Some(Id::new(1)));
+
+    let order_id: Id<Order> = user_id.convert();
+    assert_eq!(order_id.into_inner(), 1);
+    assert_eq!(std::mem::size_of::<Id<Order>>(), std::mem::size_of::<i64>());
+
+    let name: Name<User> = String::from("name").into();
+    assert_eq!(name.clone(), name);
+    assert_eq!(name.len(), 4);
+
+    let token: Token<User> = Token::new("token".into());
+    assert_eq!(token.clone().expose_secret(), "token");
+    assert!(format!("{token:?}").contains("REDACTED"));
+
+    assert_send_sync::<Id<*const u8>>();
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
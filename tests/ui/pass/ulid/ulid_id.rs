@@ -0,0 +1,25 @@
+use microtype::{ulid::Ulid, Microtype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy)]
+    #[id(ulid)]
+    Ulid {
+        EventId
+    }
+}
+
+fn main() {
+    let first = EventId::generate();
+    let second = EventId::generate();
+    assert_ne!(first, second);
+    assert!(first.inner().timestamp_ms() <= second.inner().timestamp_ms());
+
+    let s = first.to_string();
+    assert_eq!(s.len(), 26);
+    assert_eq!(s.parse::<EventId>().unwrap(), first);
+    "not a ulid".parse::<EventId>().unwrap_err();
+
+    let earlier = EventId::new(Ulid::from_parts(1_000, u128::MAX));
+    let later = EventId::new(Ulid::from_parts(2_000, 0));
+    assert!(earlier < later);
+}
@@ -0,0 +1,41 @@
+use std::collections::{BTreeSet, HashSet};
+
+use microtype::{uuid::Uuid, Microtype};
+
+microtype::microtype! {
+    #[derive(Debug, Clone, Copy)]
+    #[id(uuid_v7)]
+    Uuid {
+        OrderId
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    #[id(uuid_v4)]
+    Uuid {
+        SessionId
+    }
+}
+
+fn main() {
+    let first = OrderId::generate();
+    let second = OrderId::generate();
+    assert_ne!(first, second);
+    assert!(first < second);
+    assert_eq!(first.inner().get_version_num(), 7);
+
+    let s = first.to_string();
+    assert_eq!(s, first.inner().to_string());
+    assert_eq!(s.parse::<OrderId>().unwrap(), first);
+    "not a uuid".parse::<OrderId>().unwrap_err();
+
+    let session = SessionId::generate();
+    assert_eq!(session.inner().get_version_num(), 4);
+    let sessions: HashSet<_> = [session, session, SessionId::generate()].into_iter().collect();
+    assert_eq!(sessions.len(), 2);
+
+    let orders: BTreeSet<_> = (0..10).map(|_| OrderId::generate()).collect();
+    let orders: Vec<_> = orders.into_iter().collect();
+    let mut sorted = orders.clone();
+    sorted.sort_by_key(|id| id.inner().get_timestamp().unwrap().to_unix());
+    assert_eq!(orders, sorted);
+}
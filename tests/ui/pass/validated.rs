@@ -0,0 +1,74 @@
+use microtype::ValidatedMicrotype;
+
+fn not_empty(s: &String) -> Result<(), &'static str> {
+    match s.is_empty() {
+        true => Err("must not be empty"),
+        false => Ok(()),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct NotEven;
+
+impl std::fmt::Display for NotEven {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not even")
+    }
+}
+
+fn even(i: &i32) -> Result<(), NotEven> {
+    match i % 2 {
+        0 => Ok(()),
+        _ => Err(NotEven),
+    }
+}
+
+microtype::microtype! {
+    #[derive(Debug, Clone)]
+    #[validate(not_empty)]
+    String {
+        Username,
+        DisplayName,
+    }
+
+    #[string]
+    #[validate(not_empty)]
+    String {
+        Email
+    }
+
+    #[derive(Debug)]
+    #[int]
+    #[validate(even, error = NotEven)]
+    i32 {
+        Even
+    }
+}
+
+fn main() {
+    let username = Username::try_new("hello".into()).unwrap();
+    assert_eq!(username.inner(), "hello");
+    assert_eq!(username.inner().len(), 5);
+    assert_eq!(username.clone().into_inner(), "hello");
+
+    let display_name: DisplayName = username.try_convert().unwrap();
+    assert_eq!(display_name.into_inner(), "hello");
+
+    assert_eq!(Username::try_new(String::new()).unwrap_err(), "must not be empty");
+    assert!(Username::try_from(String::new()).is_err());
+
+    let email: Email = "email".parse().unwrap();
+    assert_eq!(format!("{email}"), "email");
+    assert!(Email::try_from("").is_err());
+
+    let even: Even = "4".parse().unwrap();
+    assert_eq!(format!("{even}"), "4");
+    assert_eq!(
+        "3".parse::<Even>().unwrap_err(),
+        microtype::ParseError::Invalid(NotEven)
+    );
+    assert!(matches!(
+        "asdf".parse::<Even>().unwrap_err(),
+        microtype::ParseError::Parse(_)
+    ));
+}